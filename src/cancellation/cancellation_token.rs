@@ -0,0 +1,46 @@
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use crate::Algorithm;
+use crate::output::solution::Solution;
+
+/// why the run stops (the main thread writes the output accordingly)
+#[derive(Clone, Debug)]
+pub enum StopReason {
+    OptimumProven(Box<ProvenOptimum>),
+    ///SIGINT/SIGTERM (signal number)
+    Signal(i32),
+    Timeout,
+    AllAlgorithmsFinished,
+}
+
+#[derive(Clone, Debug)]
+pub struct ProvenOptimum {
+    pub solution: Solution,
+    pub found_after: Duration,
+    pub algorithm: Option<Algorithm>,
+    //algorithm that found the solution (if known)
+    pub known_opt: bool,
+    //true if the upper bound reached the known OPT of the benchmark (tmp_opt) instead of the lower bound
+}
+
+/// shared by all schedulers: the first cancel sets the stop reason, the schedulers stop at their next check and only the main thread writes the output
+#[derive(Debug, Default)]
+pub struct CancellationToken {
+    stop_reason: OnceLock<StopReason>,
+}
+
+impl CancellationToken {
+    /// => false if the token was already cancelled (the first stop reason is kept)
+    pub fn cancel(&self, stop_reason: StopReason) -> bool {
+        self.stop_reason.set(stop_reason).is_ok()
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.stop_reason.get().is_some()
+    }
+
+    pub fn get_stop_reason(&self) -> Option<&StopReason> {
+        self.stop_reason.get()
+    }
+}
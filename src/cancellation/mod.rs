@@ -0,0 +1 @@
+pub mod cancellation_token;
@@ -0,0 +1,166 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::good_solutions::persistence::{LineReader, parse_good_solutions, SavedGoodSolutions, solution_to_string, write_atomically};
+use crate::input::input::Input;
+use crate::input::{MyRng, RngSeed};
+use crate::output::solution::Solution;
+
+const FILE_HEADER: &str = "MAKESPAN_CHECKPOINT";
+const FILE_VERSION: u32 = 1;
+
+/// state of a Swap worker at the beginning of a restart
+#[derive(Clone, Debug)]
+pub struct SwapWorkerState {
+    pub solution: Solution,
+    //solution the restart starts with
+    pub best_solution: Solution,
+    pub island: Vec<Solution>,
+    pub restart_after_steps: usize,
+    pub restart_possibility: f64,
+    pub total_steps: usize,
+    pub restarts: usize,
+    pub restarts_without_improvement: usize,
+    pub rng: MyRng,
+    pub rf_rng: MyRng,
+    //rng of the RF scheduler used for random restarts
+    pub swap_elapsed: Duration,
+    //time since the Swap algorithm started (time budget)
+    pub unflushed_solutions: Vec<Solution>,
+    //solutions of the worker that are not stored in the good solutions yet (ascending by c_max)
+}
+
+/// latest states of the workers (written periodically by --checkpoint) and the states of a resumed checkpoint (--resume)
+#[derive(Debug, Default)]
+pub struct Checkpoint {
+    swap_workers: Mutex<BTreeMap<(usize, usize), SwapWorkerState>>,
+    //(config id, worker index) -> latest state
+    resumed_swap_workers: Mutex<HashMap<(usize, usize), SwapWorkerState>>,
+    //(config id, worker index) -> state of the resumed checkpoint (gets taken by the worker)
+    good_solutions_snapshot: Mutex<Option<String>>,
+    //good solutions at the time of the latest worker state (see good_solutions_to_string), None => the current ones get saved
+}
+
+/// content of a checkpoint file
+pub struct ResumedCheckpoint {
+    pub checkpoint: Checkpoint,
+    pub elapsed: Duration,
+    //run time before the checkpoint was written
+    pub shared_initial_rng: MyRng,
+    pub good_solutions: SavedGoodSolutions,
+}
+
+impl Checkpoint {
+    /// the good solutions get saved together with the state => the restarts of a resumed worker draw from the same good solutions
+    pub fn update_swap_worker(&self, config_id: usize, worker_index: usize, state: SwapWorkerState, good_solutions_str: String) {
+        let mut swap_workers = self.swap_workers.lock().unwrap();
+        swap_workers.insert((config_id, worker_index), state);
+        *self.good_solutions_snapshot.lock().unwrap() = Some(good_solutions_str);
+    }
+
+    /// has to be called when the Swap workers are finished (the good solutions may change afterwards)
+    pub fn clear_good_solutions_snapshot(&self) {
+        *self.good_solutions_snapshot.lock().unwrap() = None;
+    }
+
+    /// returns the state the worker should continue with (only once)
+    pub fn take_resumed_swap_worker(&self, config_id: usize, worker_index: usize) -> Option<SwapWorkerState> {
+        self.resumed_swap_workers.lock().unwrap().remove(&(config_id, worker_index))
+    }
+
+    /// writes the run time, the state of the shared initial rng, the good solutions (incl. bounds, see good_solutions_to_string) and the latest state of each worker into a versioned text file
+    /// the good solutions of the latest worker state are used instead of the current ones (good_solutions_str) if there is one
+    pub fn save(&self, path: &Path, elapsed: Duration, shared_initial_rng: &MyRng, good_solutions_str: &str) -> std::io::Result<()> {
+        let swap_workers = self.swap_workers.lock().unwrap();
+        let mut out = format!("{FILE_HEADER} {FILE_VERSION}\nELAPSED_MILLIS {}\nSHARED_RNG {}\n", elapsed.as_millis(), rng_to_string(shared_initial_rng));
+        out.push_str(self.good_solutions_snapshot.lock().unwrap().as_deref().unwrap_or(good_solutions_str));
+
+        //workers that haven't restarted since the resume keep their resumed state:
+        let mut swap_workers = swap_workers.clone();
+        for (key, state) in self.resumed_swap_workers.lock().unwrap().iter() {
+            swap_workers.entry(*key).or_insert_with(|| state.clone());
+        }
+        out.push_str(&format!("SWAP_WORKERS {}\n", swap_workers.len()));
+        for ((config_id, worker_index), state) in swap_workers {
+            out.push_str(&format!("SWAP_WORKER {config_id} {worker_index}\n"));
+            out.push_str(&format!("COUNTERS {} {} {} {} {}\n", state.restart_after_steps, state.restart_possibility.to_bits(), state.total_steps, state.restarts, state.restarts_without_improvement));
+            out.push_str(&format!("RNG {}\nRF_RNG {}\n", rng_to_string(&state.rng), rng_to_string(&state.rf_rng)));
+            out.push_str(&format!("SWAP_ELAPSED_MILLIS {}\n", state.swap_elapsed.as_millis()));
+            out.push_str(&solution_to_string(&state.solution));
+            out.push_str(&solution_to_string(&state.best_solution));
+            out.push_str(&format!("ISLAND {}\n", state.island.len()));
+            for solution in &state.island {
+                out.push_str(&solution_to_string(solution));
+            }
+            out.push_str(&format!("UNFLUSHED {}\n", state.unflushed_solutions.len()));
+            for solution in &state.unflushed_solutions {
+                out.push_str(&solution_to_string(solution));
+            }
+        }
+        write_atomically(path, out)
+    }
+
+    /// reads a file written by save (panics if it is invalid or belongs to another input)
+    pub fn load(path: &Path, input: &Input) -> ResumedCheckpoint {
+        let content = match fs::read_to_string(path) {
+            Ok(str) => str,
+            Err(e) => panic!("{}", e),
+        };
+        let mut lines = LineReader::new(&content);
+
+        let version = lines.next_line(FILE_HEADER).parse::<u32>().unwrap();
+        if version != FILE_VERSION {
+            panic!("invalid checkpoint file! => unsupported version {version} (supported: {FILE_VERSION})");
+        }
+        let elapsed = Duration::from_millis(lines.next_line("ELAPSED_MILLIS").parse::<u64>().unwrap());
+        let shared_initial_rng = rng_from_str(&lines.next_line("SHARED_RNG"));
+        let good_solutions = parse_good_solutions(&mut lines, input);
+
+        let mut resumed_swap_workers = HashMap::new();
+        let swap_worker_count = lines.next_line("SWAP_WORKERS").parse::<usize>().unwrap();
+        for _ in 0..swap_worker_count {
+            let key: Vec<usize> = lines.next_line("SWAP_WORKER").split_whitespace().map(|part| part.parse::<usize>().unwrap()).collect();
+            let counters: Vec<u64> = lines.next_line("COUNTERS").split_whitespace().map(|part| part.parse::<u64>().unwrap()).collect();
+            let rng = rng_from_str(&lines.next_line("RNG"));
+            let rf_rng = rng_from_str(&lines.next_line("RF_RNG"));
+            let swap_elapsed = Duration::from_millis(lines.next_line("SWAP_ELAPSED_MILLIS").parse::<u64>().unwrap());
+            let solution = lines.read_solution(input);
+            let best_solution = lines.read_solution(input);
+            let island_size = lines.next_line("ISLAND").parse::<usize>().unwrap();
+            let island = (0..island_size).map(|_| lines.read_solution(input)).collect();
+            let unflushed_count = lines.next_line("UNFLUSHED").parse::<usize>().unwrap();
+            let unflushed_solutions = (0..unflushed_count).map(|_| lines.read_solution(input)).collect();
+            resumed_swap_workers.insert((key[0], key[1]), SwapWorkerState {
+                solution,
+                best_solution,
+                island,
+                restart_after_steps: counters[0] as usize,
+                restart_possibility: f64::from_bits(counters[1]),
+                total_steps: counters[2] as usize,
+                restarts: counters[3] as usize,
+                restarts_without_improvement: counters[4] as usize,
+                rng,
+                rf_rng,
+                swap_elapsed,
+                unflushed_solutions,
+            });
+        }
+
+        let checkpoint = Checkpoint { swap_workers: Mutex::new(BTreeMap::new()), resumed_swap_workers: Mutex::new(resumed_swap_workers), good_solutions_snapshot: Mutex::new(None) };
+        ResumedCheckpoint { checkpoint, elapsed, shared_initial_rng, good_solutions }
+    }
+}
+
+/// seed and word position
+fn rng_to_string(rng: &MyRng) -> String {
+    format!("{} {}", rng, rng.get_word_pos())
+}
+
+fn rng_from_str(s: &str) -> MyRng {
+    let (seed, word_pos) = s.split_once(' ').unwrap();
+    RngSeed::from_str(seed).unwrap().create_rng_at(word_pos.parse::<u128>().unwrap())
+}
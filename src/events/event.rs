@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+use crate::Algorithm;
+
+/// everything observers (see EventBus) get notified about
+#[derive(Clone, Debug)]
+pub enum Event {
+    NewUpperBound { previous: u32, upper_bound: u32, algorithm: Option<Algorithm> },
+    #[allow(dead_code)] //no algorithm improves the lower bound yet (see Bounds::update_lower_bound)
+    NewLowerBound { previous: u32, lower_bound: u32, algorithm: Option<Algorithm> },
+    ///a solution got stored in the good solutions
+    NewEliteSolution { c_max: u32, algorithms: Vec<Algorithm>, new_best: bool, solution_count: usize },
+    AlgorithmStarted { algorithm: Algorithm, config_id: usize },
+    ///c_max = None => the algorithm found no solution
+    AlgorithmFinished { algorithm: Algorithm, config_id: usize, c_max: Option<u32> },
+    OptimumProven { c_max: u32, algorithm: Option<Algorithm> },
+}
+
+impl Event {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Event::NewUpperBound { .. } => "new_upper_bound",
+            Event::NewLowerBound { .. } => "new_lower_bound",
+            Event::NewEliteSolution { .. } => "new_elite_solution",
+            Event::AlgorithmStarted { .. } => "algorithm_started",
+            Event::AlgorithmFinished { .. } => "algorithm_finished",
+            Event::OptimumProven { .. } => "optimum_proven",
+        }
+    }
+
+    /// one JSON object (without line break), elapsed = time since the start of the run
+    pub fn to_json(&self, elapsed: Duration) -> String {
+        let fields = match self {
+            Event::NewUpperBound { previous, upper_bound, algorithm } => format!("\"previous\":{previous},\"upper_bound\":{upper_bound},\"algorithm\":{}", algorithm_to_json(algorithm)),
+            Event::NewLowerBound { previous, lower_bound, algorithm } => format!("\"previous\":{previous},\"lower_bound\":{lower_bound},\"algorithm\":{}", algorithm_to_json(algorithm)),
+            Event::NewEliteSolution { c_max, algorithms, new_best, solution_count } => {
+                let algorithms: Vec<String> = algorithms.iter().map(|algorithm| format!("\"{:?}\"", algorithm)).collect();
+                format!("\"c_max\":{c_max},\"algorithms\":[{}],\"new_best\":{new_best},\"solution_count\":{solution_count}", algorithms.join(","))
+            }
+            Event::AlgorithmStarted { algorithm, config_id } => format!("\"algorithm\":\"{:?}\",\"config_id\":{config_id}", algorithm),
+            Event::AlgorithmFinished { algorithm, config_id, c_max } => format!("\"algorithm\":\"{:?}\",\"config_id\":{config_id},\"c_max\":{}", algorithm, c_max.map_or(String::from("null"), |c_max| c_max.to_string())),
+            Event::OptimumProven { c_max, algorithm } => format!("\"c_max\":{c_max},\"algorithm\":{}", algorithm_to_json(algorithm)),
+        };
+        format!("{{\"time\":{},\"event\":\"{}\",{}}}", elapsed.as_secs_f64(), self.get_name(), fields)
+    }
+}
+
+fn algorithm_to_json(algorithm: &Option<Algorithm>) -> String {
+    match algorithm {
+        None => String::from("null"),
+        Some(algorithm) => format!("\"{:?}\"", algorithm),
+    }
+}
@@ -0,0 +1,49 @@
+use std::fmt::{Debug, Formatter};
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use crate::events::event::Event;
+
+/// gets notified synchronously by the thread that emitted the event
+pub trait Observer: Send + Sync {
+    /// elapsed = time since the start of the run
+    fn notify(&self, elapsed: Duration, event: &Event);
+
+    /// called before the process exits (buffered sinks write their remaining events)
+    fn flush(&self) {}
+}
+
+/// distributes the events of Bounds, GoodSolutions and the schedulers to all subscribed observers
+pub struct EventBus {
+    start_time: Instant,
+    observers: RwLock<Vec<Box<dyn Observer>>>,
+}
+
+impl EventBus {
+    pub fn new(start_time: Instant) -> Self {
+        Self { start_time, observers: RwLock::new(vec![]) }
+    }
+
+    pub fn subscribe(&self, observer: Box<dyn Observer>) {
+        self.observers.write().unwrap().push(observer);
+    }
+
+    pub fn emit(&self, event: Event) {
+        let elapsed = self.start_time.elapsed();
+        for observer in self.observers.read().unwrap().iter() {
+            observer.notify(elapsed, &event);
+        }
+    }
+
+    pub fn flush(&self) {
+        for observer in self.observers.read().unwrap().iter() {
+            observer.flush();
+        }
+    }
+}
+
+impl Debug for EventBus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "EventBus {{ observers: {} }}", self.observers.read().unwrap().len())
+    }
+}
@@ -0,0 +1,83 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::events::event::Event;
+use crate::events::event_bus::Observer;
+use crate::logging::logger::{log_for_module, LogLevel};
+
+/// the human-readable log lines (the bound lines are needed for measurements, see python_scripts/eval.py), logged under the module emitting the event (=> --log-filter)
+pub struct LogSink;
+
+impl Observer for LogSink {
+    fn notify(&self, elapsed: Duration, event: &Event) {
+        match event {
+            Event::NewUpperBound { previous, upper_bound, algorithm } => { log_for_module("global_bounds::bounds", LogLevel::Info, format!("NEW upper_bound:{}->{} (after: {:?} sec)", previous, upper_bound, elapsed.as_secs_f64()), *algorithm); }
+            Event::NewLowerBound { previous, lower_bound, algorithm } => { log_for_module("global_bounds::bounds", LogLevel::Info, format!("NEW lower_bound:{}->{} (after: {:?} sec)", previous, lower_bound, elapsed.as_secs_f64()), *algorithm); }
+            Event::AlgorithmFinished { algorithm, config_id, c_max } => { log_for_module("main", LogLevel::Debug, format!("finished {:?} algorithm (config {}) with c_max: {:?}", algorithm, config_id, c_max), None); }
+            _ => {} //the END message gets logged by the main thread
+        }
+    }
+}
+
+/// CSV file with one line per bound change: time,bound,value,algorithm (starts with the initial bounds at time 0)
+pub struct CsvTrajectorySink {
+    writer: Mutex<BufWriter<File>>,
+}
+
+impl CsvTrajectorySink {
+    pub fn create(path: &Path, (upper_bound, lower_bound): (u32, u32)) -> Self {
+        let file = File::create(path).unwrap_or_else(|e| panic!("couldn't create {:?}: {}", path, e));
+        let sink = Self { writer: Mutex::new(BufWriter::new(file)) };
+        sink.write_line(format!("time,bound,value,algorithm\n0,upper_bound,{upper_bound},initial\n0,lower_bound,{lower_bound},initial"));
+        sink
+    }
+
+    fn write_line(&self, line: String) {
+        if let Err(e) = writeln!(self.writer.lock().unwrap(), "{}", line) {
+            eprintln!("Couldn't write the trajectory: {}", e);
+        }
+    }
+}
+
+impl Observer for CsvTrajectorySink {
+    fn notify(&self, elapsed: Duration, event: &Event) {
+        let (bound, value, algorithm) = match event {
+            Event::NewUpperBound { upper_bound, algorithm, .. } => ("upper_bound", upper_bound, algorithm),
+            Event::NewLowerBound { lower_bound, algorithm, .. } => ("lower_bound", lower_bound, algorithm),
+            _ => { return; }
+        };
+        let algorithm = algorithm.map_or(String::new(), |algorithm| format!("{:?}", algorithm));
+        self.write_line(format!("{},{},{},{}", elapsed.as_secs_f64(), bound, value, algorithm));
+    }
+
+    fn flush(&self) {
+        let _ = self.writer.lock().unwrap().flush();
+    }
+}
+
+/// every event as one JSON object per line (see Event::to_json)
+pub struct JsonLinesSink {
+    writer: Mutex<BufWriter<File>>,
+}
+
+impl JsonLinesSink {
+    pub fn create(path: &Path) -> Self {
+        let file = File::create(path).unwrap_or_else(|e| panic!("couldn't create {:?}: {}", path, e));
+        Self { writer: Mutex::new(BufWriter::new(file)) }
+    }
+}
+
+impl Observer for JsonLinesSink {
+    fn notify(&self, elapsed: Duration, event: &Event) {
+        if let Err(e) = writeln!(self.writer.lock().unwrap(), "{}", event.to_json(elapsed)) {
+            eprintln!("Couldn't write the event: {}", e);
+        }
+    }
+
+    fn flush(&self) {
+        let _ = self.writer.lock().unwrap().flush();
+    }
+}
@@ -1,38 +1,44 @@
 use std::cmp::max;
-use std::process::exit;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::time::Instant;
 
-use permutation::Permutation;
 
 use crate::{Algorithm, Args};
+use crate::cancellation::cancellation_token::{CancellationToken, ProvenOptimum, StopReason};
+use crate::events::event::Event;
+use crate::events::event_bus::EventBus;
 use crate::input::input::Input;
-use crate::output::{get_directory_name, log, output_solution};
+use crate::logging::logger::{log, LogLevel};
 use crate::output::solution::Solution;
 
 pub struct Bounds {
     upper_bound: AtomicU32,
     lower_bound: AtomicU32,
     tmp_opt: Option<u32>,
+    cancellation_token: Arc<CancellationToken>,
+    //gets cancelled with the optimal solution as soon as it is proven
+    event_bus: Arc<EventBus>,
 }
 
 impl Bounds {
-    pub fn new(upper_bound: u32, lower_bound: u32, tmp_opt: Option<u32>) -> Self {
+    pub fn new(upper_bound: u32, lower_bound: u32, tmp_opt: Option<u32>, cancellation_token: Arc<CancellationToken>, event_bus: Arc<EventBus>) -> Self {
         Self {
             upper_bound: AtomicU32::new(upper_bound),
             lower_bound: AtomicU32::new(lower_bound),
             tmp_opt,
+            cancellation_token,
+            event_bus,
         }
     }
 
-    pub fn trivial(input: Arc<Input>, tmp_opt: Option<u32>) -> Self {
+    pub fn trivial(input: Arc<Input>, tmp_opt: Option<u32>, cancellation_token: Arc<CancellationToken>, event_bus: Arc<EventBus>) -> Self {
         let jobs = input.get_jobs();
         let machine_count = input.get_machine_count();
         let upper_bound = jobs.iter().sum::<u32>() / input.get_machine_count() as u32 + jobs[0];
         let lower_bound = max(jobs[machine_count - 1] + jobs[machine_count], max(jobs[0], jobs.iter().sum::<u32>().div_ceil(machine_count as u32)));
-        log(format!("using the trivial bounds: UB:{} LB:{} ", upper_bound, lower_bound), true, true, None);
-        Self::new(upper_bound, lower_bound, tmp_opt)
+        log(LogLevel::Info, format!("using the trivial bounds: UB:{} LB:{} ", upper_bound, lower_bound), None);
+        Self::new(upper_bound, lower_bound, tmp_opt, cancellation_token, event_bus)
     }
 
     /// returns (upper_bound, lower_bound)
@@ -48,6 +54,20 @@ impl Bounds {
         self.lower_bound.load(Ordering::Acquire)
     }
 
+    /// token shared by all schedulers (they stop as soon as it is cancelled)
+    pub fn get_cancellation_token(&self) -> &CancellationToken {
+        &self.cancellation_token
+    }
+
+    /// signals the proven optimum (the output gets written by the main thread)
+    fn optimum_proven(&self, solution: &Solution, start_time: Instant, currently_running_algo: Option<Algorithm>, known_opt: bool) {
+        let mut solution = solution.clone();
+        solution.set_found(self.get_bounds());
+        if self.cancellation_token.cancel(StopReason::OptimumProven(Box::new(ProvenOptimum { solution: solution.clone(), found_after: start_time.elapsed(), algorithm: currently_running_algo, known_opt }))) {
+            self.event_bus.emit(Event::OptimumProven { c_max: solution.get_data().get_c_max(), algorithm: currently_running_algo });
+        }
+    }
+
     /* pub fn set_upper_bound(&self, upper_bound: u32) {
          self.upper_bound.store(upper_bound, Ordering::Release)
      }
@@ -56,48 +76,65 @@ impl Bounds {
          self.lower_bound.store(lower_bound, Ordering::Release)
      }*/
 
-    pub fn update_bounds(&self, new_upper_bound: u32, new_lower_bound: u32, solution: &Solution, args: Arc<Args>, perm: Arc<Permutation>, start_time: Instant, currently_running_algo: Option<Algorithm>, jobs: &[u32], machine_count: usize) {
-        self.update_upper_bound(new_upper_bound, solution, Arc::clone(&args), Arc::clone(&perm), start_time, currently_running_algo, jobs, machine_count);
-        self.update_lower_bound(new_lower_bound, solution, args, perm, start_time, currently_running_algo, jobs, machine_count);
+    pub fn update_bounds(&self, new_upper_bound: u32, new_lower_bound: u32, solution: &mut Solution, args: Arc<Args>, start_time: Instant, currently_running_algo: Option<Algorithm>) {
+        self.update_upper_bound(new_upper_bound, solution, args, start_time, currently_running_algo);
+        self.update_lower_bound(new_lower_bound, solution, start_time, currently_running_algo);
     }
 
-    pub fn update_upper_bound(&self, new_upper_bound: u32, solution: &Solution, args: Arc<Args>, perm: Arc<Permutation>, start_time: Instant, currently_running_algo: Option<Algorithm>, jobs: &[u32], machine_count: usize) {
+    /// also stamps the solution with the bounds and the time it was found
+    pub fn update_upper_bound(&self, new_upper_bound: u32, solution: &mut Solution, args: Arc<Args>, start_time: Instant, currently_running_algo: Option<Algorithm>) {
         let prev = self.upper_bound.fetch_min(new_upper_bound, Ordering::AcqRel);
+        solution.set_found(self.get_bounds());
         if new_upper_bound < prev {
-            log(format!("NEW upper_bound:{}->{} (after: {:?} sec)", prev, new_upper_bound, start_time.elapsed().as_secs_f64()), true, args.measurement, currently_running_algo);
+            self.event_bus.emit(Event::NewUpperBound { previous: prev, upper_bound: new_upper_bound, algorithm: currently_running_algo });
             match self.tmp_opt { //tmp löschen
                 None => {}
                 Some(opt_c_max) => {
-                    if new_upper_bound == opt_c_max {
-                        let t = start_time.elapsed().as_secs_f64();
-                        if t <= args.timeout_after as f64 {
-                            log(format!("END after: {:?} sec (found OPT solution)", t), true, args.measurement, currently_running_algo);
-                            let input_file_name = args.path.file_stem().unwrap().to_str().unwrap();
-                            output_solution(solution, perm, args.write, get_directory_name(args.write_directory_name.clone(), input_file_name), true, args.measurement, jobs, machine_count);
-                            exit(0)
-                        }
+                    if new_upper_bound == opt_c_max && start_time.elapsed().as_secs_f64() <= args.timeout_after as f64 {
+                        self.optimum_proven(solution, start_time, currently_running_algo, true);
+                        return;
                     }
                 }
             }
             if new_upper_bound == self.get_lower_bound() {
-                log(format!("END after: {:?} sec (found OPT solution)", start_time.elapsed().as_secs_f64()), true, args.measurement, currently_running_algo);
-                let input_file_name = args.path.file_stem().unwrap().to_str().unwrap();
-                output_solution(solution, perm, args.write, get_directory_name(args.write_directory_name.clone(), input_file_name), false, args.measurement, jobs, machine_count);
-                exit(0)
+                self.optimum_proven(solution, start_time, currently_running_algo, false);
             }
         }
     }
 
-    pub fn update_lower_bound(&self, new_lower_bound: u32, solution: &Solution, args: Arc<Args>, perm: Arc<Permutation>, start_time: Instant, currently_running_algo: Option<Algorithm>, jobs: &[u32], machine_count: usize) {
-        let prev = self.upper_bound.fetch_max(new_lower_bound, Ordering::AcqRel);
+    pub fn update_lower_bound(&self, new_lower_bound: u32, solution: &Solution, start_time: Instant, currently_running_algo: Option<Algorithm>) {
+        let prev = self.lower_bound.fetch_max(new_lower_bound, Ordering::AcqRel);
         if new_lower_bound > prev {
-            log(format!("NEW lower_bound:{}->{} (after: {:?} sec)", prev, new_lower_bound, start_time.elapsed().as_secs_f64()), true, args.measurement, currently_running_algo);
+            self.event_bus.emit(Event::NewLowerBound { previous: prev, lower_bound: new_lower_bound, algorithm: currently_running_algo });
             if self.get_upper_bound() == new_lower_bound {
-                log(format!("END after: {:?} sec (found OPT solution)", start_time.elapsed().as_secs_f64()), true, args.measurement, currently_running_algo);
-                let input_file_name = args.path.file_stem().unwrap().to_str().unwrap();
-                output_solution(solution, perm, args.write, get_directory_name(args.write_directory_name.clone(), input_file_name), false, args.measurement, jobs, machine_count);
-                exit(0)
+                self.optimum_proven(solution, start_time, currently_running_algo, false);
             }
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use crate::Algorithm::LPT;
+    use crate::output::machine_jobs::MachineJobs;
+
+    use super::*;
+
+    #[test]
+    fn update_lower_bound_only_raises_the_lower_bound() {
+        let bounds = Bounds::new(20, 10, None, Arc::new(CancellationToken::default()), Arc::new(EventBus::new(Instant::now())));
+        let mut machine_jobs = MachineJobs::empty(1);
+        machine_jobs.assign_job(20, 0, 0);
+        let solution = Solution::restore(vec![LPT], None, machine_jobs);
+
+        bounds.update_lower_bound(15, &solution, Instant::now(), None);
+        assert_eq!(bounds.get_bounds(), (20, 15));
+        bounds.update_lower_bound(12, &solution, Instant::now(), None);
+        assert_eq!(bounds.get_bounds(), (20, 15));
+        assert!(!bounds.get_cancellation_token().is_cancelled());
+
+        //lower bound = upper bound => the solution is optimal:
+        bounds.update_lower_bound(20, &solution, Instant::now(), Some(LPT));
+        assert_eq!(bounds.get_bounds(), (20, 20));
+        assert!(bounds.get_cancellation_token().is_cancelled());
+    }
+}
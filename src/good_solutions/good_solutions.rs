@@ -1,55 +1,228 @@
-use std::sync::Arc;
+use std::cmp::{max, min};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Condvar, Mutex};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::Duration;
 
 use concurrent_map::ConcurrentMap;
-use permutation::Permutation;
+use rand_distr::{Distribution, Exp};
 
-use crate::output::{get_directory_name, log, output_solution};
+use crate::cancellation::cancellation_token::CancellationToken;
+use crate::events::event::Event;
+use crate::events::event_bus::EventBus;
+use crate::good_solutions::replacement_policy::ReplacementPolicy;
+use crate::good_solutions::replacement_policy::ReplacementPolicy::{Hybrid, QualityAndDistance};
+use crate::input::input::Input;
+use crate::input::MyRng;
+use crate::logging::logger::{log, LogLevel};
+use crate::output::{output_solution, OutputOptions};
+use crate::output::machine_jobs::MachineJobs;
 use crate::output::solution::Solution;
 
-/// Sorted (by c_max) Collection of the max_capacity best Solutions
+const DIVERSITY_NEIGHBORS: usize = 3; //number of closest solutions used for the diversity of a solution (hybrid replacement policy)
+
+/// Sorted (by c_max) Collection of the max_capacity best Solutions (which ones are kept depends on the replacement policy)
 #[derive(Debug, Clone)]
 pub struct GoodSolutions {
     solutions: ConcurrentMap<(u32, usize), Solution>,
     //((c_max,index),solution),... index is needed for saving multiple solutions with the same c_max
+    registry: Arc<Mutex<Registry>>,
     max_capacity: usize,
+    replacement_policy: ReplacementPolicy,
+    input: Arc<Input>,
+    //needed for the canonical form of the solutions
+    notifier: Arc<Notifier>,
+    event_bus: Arc<EventBus>,
+    cancellation_token: Arc<CancellationToken>,
+    //waiting threads stop waiting once the run gets cancelled
+}
+
+/// bookkeeping of the stored solutions (the lock also makes duplicate check, insertion and eviction atomic)
+#[derive(Debug, Default)]
+struct Registry {
+    canonical_hashes: HashMap<usize, u64>,
+    //index -> canonical hash of the stored solution (no recomputation of the canonical form on removal)
+    keys_by_canonical_hash: HashMap<u64, Vec<(u32, usize)>>,
+    //canonical hash -> keys of the stored solutions with it => duplicates (machine relabeling, swapped jobs with equal length) get found in O(1), hash collisions are ruled out by comparing the canonical forms
+    next_index: usize,
+    distances: HashMap<usize, HashMap<usize, usize>>,
+    //index -> (other index -> distance), only filled if the replacement policy uses distances
+}
+
+/// used to wake up threads that wait for (new best) solutions
+#[derive(Debug, Default)]
+struct Notifier {
+    mutex: Mutex<()>,
+    solution_added: Condvar,
+    new_best_solution_subscribers: Mutex<Vec<Sender<Solution>>>,
 }
 
 impl GoodSolutions {
-    pub fn new(max_capacity: usize) -> Self {
+    pub fn new(max_capacity: usize, replacement_policy: ReplacementPolicy, input: Arc<Input>, event_bus: Arc<EventBus>, cancellation_token: Arc<CancellationToken>) -> Self {
         debug_assert!(max_capacity >= 1);
-        Self { solutions: ConcurrentMap::new(), max_capacity }
+        Self { solutions: ConcurrentMap::new(), registry: Arc::new(Mutex::new(Registry::default())), max_capacity, replacement_policy, input, notifier: Arc::new(Notifier::default()), event_bus, cancellation_token }
+    }
+
+    /// blocks until at least n solutions are stored, the run gets cancelled or the timeout (None => no timeout) is reached
+    /// returns whether there are n solutions
+    pub fn wait_for_count(&self, n: usize, timeout: Option<Duration>) -> bool {
+        let guard = self.notifier.mutex.lock().unwrap();
+        let keep_waiting = |_: &mut ()| self.get_solution_count() < n && !self.cancellation_token.is_cancelled();
+        match timeout {
+            None => {
+                let _guard = self.notifier.solution_added.wait_while(guard, keep_waiting).unwrap();
+            }
+            Some(timeout) => {
+                let _guard = self.notifier.solution_added.wait_timeout_while(guard, timeout, keep_waiting).unwrap();
+            }
+        }
+        self.get_solution_count() >= n
+    }
+
+    /// wakes up all threads in wait_for_count (called after the cancellation token got cancelled)
+    pub fn notify_cancellation(&self) {
+        let _guard = self.notifier.mutex.lock().unwrap();
+        self.notifier.solution_added.notify_all();
+    }
+
+    /// every new best solution gets sent to the returned receiver
+    pub fn subscribe_to_new_best_solutions(&self) -> Receiver<Solution> {
+        let (sender, receiver) = channel();
+        self.notifier.new_best_solution_subscribers.lock().unwrap().push(sender);
+        receiver
     }
 
     pub fn add_solution(&self, new_solution: Solution) {
         if new_solution.is_satisfiable() {
+            let jobs = self.input.get_jobs();
             let new_c_max = new_solution.get_data().get_c_max();
-            let mut new_index: usize = 0;
-
-            //check if new_solution is actually new:
-            if self.solutions.contains_key(&(new_c_max, 0)) {
-                //there is already at least one solution with the same c_max
-                let mut solutions_to_check: Vec<_> = self.solutions.range((new_c_max, 0)..(new_c_max + 1, 0)).collect();
-                for (_, solution) in &solutions_to_check {
-                    if new_solution == *solution {
-                        //new_solution is not new
-                        return;
+            let new_canonical_form = new_solution.get_canonical_form(jobs);
+            let new_canonical_hash = MachineJobs::hash_canonical_form(&new_canonical_form);
+
+            //a full pool only gets better => without diversity based removal a solution that is no better than the worst one would be removed right away:
+            if !matches!(self.replacement_policy, Hybrid(_)) && self.solutions.len() >= self.max_capacity && self.solutions.last().is_some_and(|((worst_c_max, _), _)| new_c_max >= worst_c_max) {
+                return;
+            }
+
+            //distances to the stored solutions: (key, distance), O(k*m³) => computed before the registry gets locked
+            let distance_to = |solution: &Solution| new_solution.get_data().get_machine_jobs().distance(solution.get_data().get_machine_jobs(), jobs);
+            let mut distances: Vec<((u32, usize), usize)> = match self.replacement_policy.uses_distances() {
+                true => { self.solutions.iter().map(|(key, solution)| (key, distance_to(&solution))).collect() }
+                false => { vec![] }
+            };
+
+            let (is_new_best, stored) = {
+                let mut registry = self.registry.lock().unwrap();
+                //check if new_solution is actually new:
+                if self.contains_canonical_form(&registry, new_canonical_hash, &new_canonical_form) {
+                    return;
+                }
+
+                if self.replacement_policy.uses_distances() {
+                    //the stored solutions may have changed in the meantime:
+                    distances.retain(|(key, _)| self.solutions.get(key).is_some());
+                    let known_keys: HashSet<(u32, usize)> = distances.iter().map(|&(key, _)| key).collect();
+                    let added_distances: Vec<((u32, usize), usize)> = self.solutions.iter().filter(|(key, _)| !known_keys.contains(key)).map(|(key, solution)| (key, distance_to(&solution))).collect();
+                    distances.extend(added_distances);
+                }
+                if let QualityAndDistance(min_distance) = self.replacement_policy {
+                    if let Some(&(closest_key, distance)) = distances.iter().min_by_key(|(_, distance)| *distance) {
+                        if distance < min_distance {
+                            if new_c_max >= closest_key.0 {
+                                return; //too close to a solution that is at least as good
+                            }
+                            self.remove_solution(&mut registry, closest_key);
+                        }
                     }
                 }
-                //new_solution is actually new
-                let old_index = match solutions_to_check.pop() {
-                    None => { return; } //tritt nur ein, wenn solutions_to_check leer ist wegen paralleler Ausführung -> sonst bug
-                    Some(((_, i), _)) => { i }
+
+                let is_new_best = match self.solutions.first() {
+                    None => { true }
+                    Some(((best_c_max, _), _)) => { new_c_max < best_c_max }
                 };
-                new_index = old_index + 1;
+                let new_index = registry.next_index;
+                registry.next_index += 1;
+                registry.canonical_hashes.insert(new_index, new_canonical_hash);
+                registry.keys_by_canonical_hash.entry(new_canonical_hash).or_default().push((new_c_max, new_index));
+                for ((_, index), distance) in distances {
+                    registry.distances.entry(index).or_default().insert(new_index, distance);
+                    registry.distances.entry(new_index).or_default().insert(index, distance);
+                }
+                self.solutions.insert((new_c_max, new_index), new_solution.clone());
+
+                while self.solutions.len() > self.max_capacity {
+                    //too many solutions saved
+                    let removed_key = match self.replacement_policy {
+                        Hybrid(diversity_weight) => { self.get_worst_hybrid_key(&registry, diversity_weight) }
+                        _ => { self.solutions.last().unwrap().0 }
+                    };
+                    self.remove_solution(&mut registry, removed_key);
+                }
+                (is_new_best, self.solutions.get(&(new_c_max, new_index)).is_some())
+            };
+
+            if stored {
+                self.event_bus.emit(Event::NewEliteSolution { c_max: new_c_max, algorithms: new_solution.get_used_algorithms().to_vec(), new_best: is_new_best, solution_count: self.get_solution_count() });
+            }
+            if is_new_best {
+                //subscribers that dropped their receiver get removed:
+                self.notifier.new_best_solution_subscribers.lock().unwrap().retain(|subscriber| subscriber.send(new_solution.clone()).is_ok());
+            }
+            let _guard = self.notifier.mutex.lock().unwrap();
+            self.notifier.solution_added.notify_all();
+        }
+    }
+
+    /// whether a stored solution has the given canonical form (registry has to be locked)
+    fn contains_canonical_form(&self, registry: &Registry, canonical_hash: u64, canonical_form: &[Vec<u32>]) -> bool {
+        registry.keys_by_canonical_hash.get(&canonical_hash).is_some_and(|keys| keys.iter().any(|key| {
+            self.solutions.get(key).is_some_and(|solution| solution.get_canonical_form(self.input.get_jobs()) == canonical_form)
+        }))
+    }
+
+    /// removes the solution and its bookkeeping (registry has to be locked)
+    fn remove_solution(&self, registry: &mut Registry, key: (u32, usize)) {
+        self.solutions.remove(&key);
+        if let Some(canonical_hash) = registry.canonical_hashes.remove(&key.1) {
+            if let Some(keys) = registry.keys_by_canonical_hash.get_mut(&canonical_hash) {
+                keys.retain(|&other_key| other_key != key);
+                if keys.is_empty() {
+                    registry.keys_by_canonical_hash.remove(&canonical_hash);
+                }
             }
-            self.solutions.insert((new_c_max, new_index), new_solution);
-            while self.solutions.len() > self.max_capacity {
-                //too many solutions saved
-                self.solutions.pop_last();
+        }
+        if let Some(distances) = registry.distances.remove(&key.1) {
+            for index in distances.keys() {
+                if let Some(other_distances) = registry.distances.get_mut(index) {
+                    other_distances.remove(&key.1);
+                }
             }
         }
     }
 
+    /// key of the solution with the worst (1-w) * c_max rank + w * diversity rank (the best solution is never chosen), O(k² log k)
+    fn get_worst_hybrid_key(&self, registry: &Registry, diversity_weight: f64) -> (u32, usize) {
+        let keys: Vec<(u32, usize)> = self.solutions.iter().map(|(key, _)| key).collect(); //sorted by c_max => position = c_max rank
+
+        //avg distance to the DIVERSITY_NEIGHBORS closest solutions:
+        let diversities: Vec<f64> = keys.iter().map(|(_, index)| {
+            let mut distances: Vec<usize> = registry.distances.get(index).map(|distances| distances.values().copied().collect()).unwrap_or_default();
+            distances.sort();
+            distances.truncate(DIVERSITY_NEIGHBORS);
+            if distances.is_empty() { 0.0 } else { distances.iter().sum::<usize>() as f64 / distances.len() as f64 }
+        }).collect();
+        let mut by_diversity: Vec<usize> = (0..keys.len()).collect();
+        by_diversity.sort_by(|&a, &b| diversities[b].total_cmp(&diversities[a])); //most diverse first
+        let mut diversity_ranks = vec![0; keys.len()];
+        for (rank, position) in by_diversity.into_iter().enumerate() {
+            diversity_ranks[position] = rank;
+        }
+
+        let fitness = |position: usize| (1.0 - diversity_weight) * position as f64 + diversity_weight * diversity_ranks[position] as f64;
+        let worst_position = (1..keys.len()).max_by(|&a, &b| fitness(a).total_cmp(&fitness(b))).unwrap_or(keys.len() - 1);
+        keys[worst_position]
+    }
+
     /// returns cloned best solution or None
     pub fn get_best_solution(&self) -> Option<Solution> {
         match self.solutions.first() {
@@ -79,6 +252,21 @@ impl GoodSolutions {
     }
 
     /// returns cloned best n solutions (or fewer, when there are no n solutions)
+    /// chooses two different good solutions, the x_1-th and x_2-th best ones (using exp. distribution), there have to be at least 2 solutions
+    pub fn select_two_solutions(&self, lambda: f64, rng: &mut MyRng) -> (Solution, Solution) {
+        let exp = Exp::new(lambda).unwrap();
+        let solution_count = self.get_solution_count();
+        debug_assert!(solution_count >= 2);
+        //x_1 isn't the last index => x_1 + 1 exists:
+        let x_1 = min(exp.sample(rng.get_mut()) as usize, solution_count - 2);
+        let mut x_2 = min(exp.sample(rng.get_mut()) as usize, solution_count - 1);
+        if x_2 == x_1 {
+            x_2 += 1;
+        }
+        let solutions = self.get_best_solutions(max(x_1, x_2) + 1);
+        (solutions[x_1].clone(), solutions[x_2].clone())
+    }
+
     pub fn get_best_solutions(&self, n: usize) -> Vec<Solution> {
         let mut solutions = vec![];
         for (_, solution) in self.solutions.iter() {
@@ -98,18 +286,71 @@ impl GoodSolutions {
         self.max_capacity
     }
 
-    pub fn write_output(&self, perm: Arc<Permutation>, write: bool, directory_name: Option<String>, input_file_name: &str, write_separate_files: bool, measurement: bool, jobs: &[u32], machine_count: usize) {
-        log(String::from("writing output..."), false, measurement, None);
-
+    pub fn write_output(&self, options: &OutputOptions, jobs: &[u32], machine_count: usize) {
+        log(LogLevel::Debug, String::from("writing output..."), None);
 
-        let directory_name_str = get_directory_name(directory_name, input_file_name);
-
-        if measurement {
-            output_solution(&self.get_best_solution().unwrap(), Arc::clone(&perm), write, directory_name_str.clone(), write_separate_files, measurement, jobs, machine_count);
+        if options.measurement {
+            output_solution(&self.get_best_solution().unwrap(), options, jobs, machine_count);
         } else {
             for (_, solution) in self.solutions.iter() {
-                output_solution(&solution, Arc::clone(&perm), write, directory_name_str.clone(), write_separate_files, measurement, jobs, machine_count);
+                output_solution(&solution, options, jobs, machine_count);
             }
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use std::time::Instant;
+
+    use crate::Algorithm::LPT;
+    use crate::cancellation::cancellation_token::StopReason;
+    use crate::input::RngSeed;
+
+    use super::*;
+
+    fn good_solutions(input: &Arc<Input>, schedules: &[&[&[usize]]]) -> GoodSolutions {
+        let good_solutions = GoodSolutions::new(10, ReplacementPolicy::Quality, Arc::clone(input), Arc::new(EventBus::new(Instant::now())), Arc::new(CancellationToken::default()));
+        for machines in schedules {
+            let mut machine_jobs = MachineJobs::empty(input.get_machine_count());
+            for (machine_index, job_indices) in machines.iter().enumerate() {
+                for &job_index in job_indices.iter() {
+                    machine_jobs.assign_job(input.get_jobs()[job_index], machine_index, job_index);
+                }
+            }
+            good_solutions.add_solution(Solution::restore(vec![LPT], None, machine_jobs));
+        }
+        good_solutions
+    }
+
+    #[test]
+    fn selects_two_different_solutions() {
+        let input = Arc::new(Input::new(2, vec![5, 4, 3, 2]));
+        let mut rng = RngSeed::from_str("[7]").unwrap().create_rng();
+        let schedules: [&[&[usize]]; 3] = [&[&[0, 3], &[1, 2]], &[&[0, 1], &[2, 3]], &[&[0, 2], &[1, 3]]];
+        //2 solutions => every x >= 1 means the last one:
+        for solution_count in [2, 3] {
+            let good_solutions = good_solutions(&input, &schedules[..solution_count]);
+            assert_eq!(good_solutions.get_solution_count(), solution_count);
+            for lambda in [0.1, 0.5, 2.0] {
+                for _ in 0..200 {
+                    let (solution_1, solution_2) = good_solutions.select_two_solutions(lambda, &mut rng);
+                    assert_ne!(solution_1.get_data().get_machine_jobs(), solution_2.get_data().get_machine_jobs());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn cancellation_wakes_up_waiting_threads() {
+        let input = Arc::new(Input::new(2, vec![5, 4, 3, 2]));
+        let cancellation_token = Arc::new(CancellationToken::default());
+        let good_solutions = GoodSolutions::new(10, ReplacementPolicy::Quality, input, Arc::new(EventBus::new(Instant::now())), Arc::clone(&cancellation_token));
+        let waiting_solutions = good_solutions.clone();
+        let waiter = std::thread::spawn(move || waiting_solutions.wait_for_count(1, None));
+        std::thread::sleep(Duration::from_millis(50));
+        cancellation_token.cancel(StopReason::Timeout);
+        good_solutions.notify_cancellation();
+        assert!(!waiter.join().unwrap());
+    }
+}
@@ -1,9 +1,15 @@
 use std::sync::{Arc, Mutex};
 
+use crate::cancellation::cancellation_token::CancellationToken;
+use crate::events::event_bus::EventBus;
 use crate::good_solutions::good_solutions::GoodSolutions;
+use crate::good_solutions::replacement_policy::ReplacementPolicy;
+use crate::input::input::Input;
 
 pub mod good_solutions;
+pub mod persistence;
+pub mod replacement_policy;
 
-pub fn create_good_solutions(max_capacity: usize) -> Arc<Mutex<GoodSolutions>> {
-    Arc::new(Mutex::new(GoodSolutions::new(max_capacity)))
+pub fn create_good_solutions(max_capacity: usize, replacement_policy: ReplacementPolicy, input: Arc<Input>, event_bus: Arc<EventBus>, cancellation_token: Arc<CancellationToken>) -> Arc<Mutex<GoodSolutions>> {
+    Arc::new(Mutex::new(GoodSolutions::new(max_capacity, replacement_policy, input, event_bus, cancellation_token)))
 }
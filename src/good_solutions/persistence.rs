@@ -0,0 +1,202 @@
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use clap::ValueEnum;
+
+use crate::Algorithm;
+use crate::global_bounds::bounds::Bounds;
+use crate::good_solutions::good_solutions::GoodSolutions;
+use crate::input::input::Input;
+use crate::input::RngSeed;
+use crate::output::machine_jobs::MachineJobs;
+use crate::output::solution::Solution;
+
+const FILE_HEADER: &str = "MAKESPAN_GOOD_SOLUTIONS";
+const FILE_VERSION: u32 = 1;
+
+/// content of a good solutions file
+pub struct SavedGoodSolutions {
+    pub solutions: Vec<Solution>,
+    //sorted by c_max
+    pub upper_bound: u32,
+    pub lower_bound: u32,
+    pub rng_seed: RngSeed,
+}
+
+/// writes all good solutions with the bounds and the rng seed of the run into a versioned text file (see good_solutions_to_string)
+pub fn save_good_solutions(path: &Path, good_solutions: &GoodSolutions, bounds: &Bounds, rng_seed: &RngSeed, input: &Input) -> std::io::Result<()> {
+    write_atomically(path, good_solutions_to_string(good_solutions, bounds, rng_seed, input))
+}
+
+/// format: header + version, input fingerprint, bounds, rng seed, then the solutions (see solution_to_string)
+pub fn good_solutions_to_string(good_solutions: &GoodSolutions, bounds: &Bounds, rng_seed: &RngSeed, input: &Input) -> String {
+    let solutions = good_solutions.get_best_solutions(good_solutions.get_solution_count());
+    let (upper_bound, lower_bound) = bounds.get_bounds();
+
+    let mut out = format!("{FILE_HEADER} {FILE_VERSION}\nINPUT {}\nBOUNDS {upper_bound} {lower_bound}\nRNG_SEED {rng_seed}\nSOLUTIONS {}\n", input_fingerprint(input), solutions.len());
+    for solution in &solutions {
+        out.push_str(&solution_to_string(solution));
+    }
+    out
+}
+
+/// c_max + used algorithms, config lines, one line of (sorted) job indices per machine
+pub fn solution_to_string(solution: &Solution) -> String {
+    let algorithms: Vec<String> = solution.get_used_algorithms().iter().map(|algorithm| format!("{:?}", algorithm)).collect();
+    let mut out = format!("SOLUTION {} {}\n", solution.get_data().get_c_max(), algorithms.join(","));
+    let config_lines: Vec<&str> = solution.get_used_config().map(|config| config.split('\n').collect()).unwrap_or_default();
+    out.push_str(&format!("CONFIG {}\n", config_lines.len()));
+    for line in config_lines {
+        out.push_str(line);
+        out.push('\n');
+    }
+    for (_, job_indices) in solution.get_data().get_machine_jobs().as_slice() {
+        let job_indices: Vec<String> = job_indices.iter().map(|job_index| job_index.to_string()).collect();
+        out.push_str(&format!("MACHINE {}\n", job_indices.join(" ")));
+    }
+    out
+}
+
+/// writes to a temporary file first => an existing file stays intact if the run gets killed
+pub fn write_atomically(path: &Path, content: String) -> std::io::Result<()> {
+    let tmp_path = path.with_extension("tmp");
+    fs::write(&tmp_path, content)?;
+    fs::rename(tmp_path, path)
+}
+
+/// reads a file written by save_good_solutions (panics if it is invalid or belongs to another input)
+pub fn load_good_solutions(path: &Path, input: &Input) -> SavedGoodSolutions {
+    let content = match fs::read_to_string(path) {
+        Ok(str) => str,
+        Err(e) => panic!("{}", e),
+    };
+    parse_good_solutions(&mut LineReader::new(&content), input)
+}
+
+pub fn parse_good_solutions(lines: &mut LineReader, input: &Input) -> SavedGoodSolutions {
+    let version = lines.next_line(FILE_HEADER).parse::<u32>().unwrap();
+    if version != FILE_VERSION {
+        panic!("invalid good solutions file! => unsupported version {version} (supported: {FILE_VERSION})");
+    }
+    if lines.next_line("INPUT") != input_fingerprint(input) {
+        panic!("invalid good solutions file! => it belongs to another input");
+    }
+    let bounds: Vec<u32> = lines.next_line("BOUNDS").split_whitespace().map(|bound| bound.parse::<u32>().unwrap()).collect();
+    let rng_seed = RngSeed::from_str(&lines.next_line("RNG_SEED")).unwrap();
+    let solution_count = lines.next_line("SOLUTIONS").parse::<usize>().unwrap();
+    let solutions = (0..solution_count).map(|_| lines.read_solution(input)).collect();
+
+    SavedGoodSolutions { solutions, upper_bound: bounds[0], lower_bound: bounds[1], rng_seed }
+}
+
+/// reads the line based files (panics if a line doesn't have the expected prefix)
+pub struct LineReader<'a> {
+    lines: std::str::Lines<'a>,
+}
+
+impl<'a> LineReader<'a> {
+    pub fn new(content: &'a str) -> Self {
+        Self { lines: content.lines() }
+    }
+
+    /// returns the rest of the next line after the prefix
+    pub fn next_line(&mut self, prefix: &str) -> String {
+        match self.lines.next().and_then(|line| line.strip_prefix(prefix)) {
+            Some(rest) => rest.trim_start().to_string(),
+            None => panic!("invalid file! => expected line starting with {prefix}"),
+        }
+    }
+
+    /// returns the next line unchanged (e.g. config lines, whose whitespace has to be kept)
+    pub fn raw_line(&mut self) -> String {
+        match self.lines.next() {
+            Some(line) => line.to_string(),
+            None => panic!("invalid file! => unexpected end of file"),
+        }
+    }
+
+    /// reads a solution written by solution_to_string
+    pub fn read_solution(&mut self, input: &Input) -> Solution {
+        let jobs = input.get_jobs();
+        let solution_line = self.next_line("SOLUTION");
+        let (c_max, algorithms) = solution_line.split_once(' ').unwrap();
+        let used_algorithms: Vec<Algorithm> = algorithms.split(',').map(|name| {
+            match Algorithm::value_variants().iter().find(|algorithm| format!("{:?}", algorithm) == name) {
+                Some(algorithm) => *algorithm,
+                None => panic!("invalid file! => unknown algorithm {name}"),
+            }
+        }).collect();
+
+        let config_line_count = self.next_line("CONFIG").parse::<usize>().unwrap();
+        let config_lines: Vec<String> = (0..config_line_count).map(|_| self.raw_line()).collect();
+        let used_config = if config_lines.is_empty() { None } else { Some(config_lines.join("\n")) };
+
+        let mut machine_jobs = MachineJobs::empty(input.get_machine_count());
+        for machine_index in 0..input.get_machine_count() {
+            for job_index in self.next_line("MACHINE").split_whitespace().map(|job_index| job_index.parse::<usize>().unwrap()) {
+                machine_jobs.assign_job(jobs[job_index], machine_index, job_index);
+            }
+        }
+        if machine_jobs.get_c_max().to_string() != c_max {
+            panic!("invalid file! => c_max {c_max} doesn't match the schedule");
+        }
+        Solution::restore(used_algorithms, used_config, machine_jobs)
+    }
+}
+
+/// job count, machine count, sum and sum of squares of the job lengths (detects files of other inputs)
+fn input_fingerprint(input: &Input) -> String {
+    let jobs = input.get_jobs();
+    let square_sum: u64 = jobs.iter().map(|&job_length| job_length as u64 * job_length as u64).sum();
+    format!("{} {} {} {}", jobs.len(), input.get_machine_count(), jobs.iter().map(|&job_length| job_length as u64).sum::<u64>(), square_sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Instant;
+
+    use crate::Algorithm::{LPT, RF, Swap};
+    use crate::cancellation::cancellation_token::CancellationToken;
+    use crate::events::event_bus::EventBus;
+    use crate::good_solutions::replacement_policy::ReplacementPolicy;
+
+    use super::*;
+
+    fn solution(input: &Input, machines: &[&[usize]], used_algorithms: Vec<Algorithm>, used_config: Option<&str>) -> Solution {
+        let mut machine_jobs = MachineJobs::empty(input.get_machine_count());
+        for (machine_index, job_indices) in machines.iter().enumerate() {
+            for &job_index in job_indices.iter() {
+                machine_jobs.assign_job(input.get_jobs()[job_index], machine_index, job_index);
+            }
+        }
+        Solution::restore(used_algorithms, used_config.map(String::from), machine_jobs)
+    }
+
+    #[test]
+    fn saved_good_solutions_round_trip() {
+        let input = Arc::new(Input::new(3, vec![9, 7, 5, 5, 3, 1]));
+        let event_bus = Arc::new(EventBus::new(Instant::now()));
+        let cancellation_token = Arc::new(CancellationToken::default());
+        let bounds = Arc::new(Bounds::new(14, 10, None, Arc::clone(&cancellation_token), Arc::clone(&event_bus)));
+        let good_solutions = GoodSolutions::new(5, ReplacementPolicy::Quality, Arc::clone(&input), Arc::clone(&event_bus), cancellation_token);
+        good_solutions.add_solution(solution(&input, &[&[0, 5], &[1, 2], &[3, 4]], vec![LPT], Some("LPT_CONFIG: A:1")));
+        good_solutions.add_solution(solution(&input, &[&[0, 4], &[1, 5], &[2, 3]], vec![RF, Swap], Some("RF_CONFIG: RNG:[1/2] ;FAILS_UNTIL_CHECK:8\n  SWAP_CONFIG:  indented; trailing space \n\tTAB")));
+        good_solutions.add_solution(solution(&input, &[&[0], &[1, 2], &[3, 4, 5]], vec![Swap], None));
+        let rng_seed = RngSeed::from_str("[1/2/3/4/5/6/7/8/9/10/11/12/13/14/15/16/17/18/19/20/21/22/23/24/25/26/27/28/29/30/31/32]").unwrap();
+
+        let saved = good_solutions_to_string(&good_solutions, &bounds, &rng_seed, &input);
+        let loaded = parse_good_solutions(&mut LineReader::new(&saved), &input);
+
+        assert_eq!((loaded.upper_bound, loaded.lower_bound), (14, 10));
+        assert_eq!(loaded.rng_seed.to_string(), rng_seed.to_string());
+        let original_solutions = good_solutions.get_best_solutions(good_solutions.get_solution_count());
+        assert_eq!(loaded.solutions.len(), 3);
+        for (loaded_solution, original_solution) in loaded.solutions.iter().zip(original_solutions.iter()) {
+            assert_eq!(loaded_solution, original_solution);
+            assert_eq!(loaded_solution.get_used_algorithms(), original_solution.get_used_algorithms());
+            assert_eq!(loaded_solution.get_used_config(), original_solution.get_used_config());
+        }
+    }
+}
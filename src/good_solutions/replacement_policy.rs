@@ -0,0 +1,57 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::good_solutions::replacement_policy::ReplacementPolicy::{Hybrid, Quality, QualityAndDistance};
+
+///Which solutions are kept when the good solutions are full (distance = jobs to move, modulo machine symmetry)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReplacementPolicy {
+    ///the solution with the worst c_max gets removed
+    Quality,
+    ///a new solution closer than min_distance to a stored one replaces it if it is better and gets rejected otherwise, else like Quality
+    QualityAndDistance(usize),
+    ///the solution with the worst (1-w) * c_max rank + w * diversity rank gets removed (diversity = avg distance to the closest solutions, the best solution is never removed)
+    Hybrid(f64),
+}
+
+impl ReplacementPolicy {
+    /// whether the distances between the stored solutions are needed
+    pub fn uses_distances(&self) -> bool {
+        *self != Quality
+    }
+}
+
+impl FromStr for ReplacementPolicy {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "quality" => Ok(Quality),
+            "quality-and-distance" => Ok(QualityAndDistance(3)),
+            "hybrid" => Ok(Hybrid(0.5)),
+            _ => {
+                if let Some(min_distance) = input.strip_prefix("quality-and-distance-") {
+                    return Ok(QualityAndDistance(min_distance.parse::<usize>().map_err(|_| format!("invalid variant: {input}"))?));
+                }
+                if let Some(diversity_weight) = input.strip_prefix("hybrid-") {
+                    let diversity_weight = diversity_weight.parse::<f64>().map_err(|_| format!("invalid variant: {input}"))?;
+                    if !(0.0..=1.0).contains(&diversity_weight) {
+                        return Err(format!("the diversity weight has to be in [0,1]: {input}"));
+                    }
+                    return Ok(Hybrid(diversity_weight));
+                }
+                Err(format!("invalid variant: {input}"))
+            }
+        }
+    }
+}
+
+impl Display for ReplacementPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Quality => write!(f, "quality"),
+            QualityAndDistance(min_distance) => write!(f, "quality-and-distance-{min_distance}"),
+            Hybrid(diversity_weight) => write!(f, "hybrid-{diversity_weight}"),
+        }
+    }
+}
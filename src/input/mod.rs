@@ -8,18 +8,18 @@ use rand::{Rng, SeedableRng, thread_rng};
 use rand_chacha::ChaCha8Rng;
 
 use crate::input::sorted_input::SortedInput;
-use crate::output::log;
+use crate::logging::logger::{log, LogLevel};
 
 pub mod input;
 pub mod sorted_input;
 
-pub fn get_input(path_buf: &PathBuf, measurement: bool) -> SortedInput {
-    let input_str = read_input(path_buf, measurement);
-    parse_input(&input_str, measurement)
+pub fn get_input(path_buf: &PathBuf) -> SortedInput {
+    let input_str = read_input(path_buf);
+    parse_input(&input_str)
 }
 
-fn read_input(path_buf: &PathBuf, measurement: bool) -> String {
-    log(String::from("reading input..."), false, measurement, None);
+fn read_input(path_buf: &PathBuf) -> String {
+    log(LogLevel::Debug, String::from("reading input..."), None);
 
     match fs::read_to_string(path_buf) {
         Ok(str) => str,
@@ -27,8 +27,8 @@ fn read_input(path_buf: &PathBuf, measurement: bool) -> String {
     }
 }
 
-fn parse_input(input_str: &str, measurement: bool) -> SortedInput {
-    log(String::from("parsing input..."), false, measurement, None);
+fn parse_input(input_str: &str) -> SortedInput {
+    log(LogLevel::Debug, String::from("parsing input..."), None);
 
     let mut split = match input_str.contains(";") {
         true => {
@@ -79,6 +79,11 @@ impl MyRng {
     pub fn get_mut(&mut self) -> &mut ChaCha8Rng {
         &mut self.0
     }
+
+    /// position in the key stream (seed + word position => exact state of the rng)
+    pub fn get_word_pos(&self) -> u128 {
+        self.0.get_word_pos()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -88,6 +93,13 @@ impl RngSeed {
     pub fn create_rng(&self) -> MyRng {
         MyRng(ChaCha8Rng::from_seed(self.0))
     }
+
+    /// recreates an rng that already generated numbers up to word_pos (see MyRng::get_word_pos)
+    pub fn create_rng_at(&self, word_pos: u128) -> MyRng {
+        let mut rng = ChaCha8Rng::from_seed(self.0);
+        rng.set_word_pos(word_pos);
+        MyRng(rng)
+    }
 }
 
 impl FromStr for RngSeed {
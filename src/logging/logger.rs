@@ -0,0 +1,138 @@
+use std::fs::File;
+use std::io::{LineWriter, stderr, stdout, Write};
+use std::panic::Location;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Mutex, OnceLock};
+
+use chrono::Local;
+use clap::ValueEnum;
+use rayon::current_thread_index;
+
+use crate::Algorithm;
+use crate::output::escape_json;
+
+static LOGGER: OnceLock<Logger> = OnceLock::new();
+
+#[derive(Clone, Copy, ValueEnum, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    ///everything needed for measurements (see python_scripts/eval.py)
+    Info,
+    Debug,
+    Trace,
+}
+
+#[derive(Clone, Copy, ValueEnum, Debug, PartialEq, Eq)]
+pub enum LogFormat {
+    ///thread_{index}_{algorithm}: {message}
+    Text,
+    ///one JSON object per line with timestamp, level, module, thread index, algorithm and message
+    Json,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LogTarget {
+    Stdout,
+    Stderr,
+    File(PathBuf),
+}
+
+impl FromStr for LogTarget {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "stdout" => Ok(LogTarget::Stdout),
+            "stderr" => Ok(LogTarget::Stderr),
+            "" => Err(format!("invalid variant: {input}")),
+            path => Ok(LogTarget::File(PathBuf::from(path))),
+        }
+    }
+}
+
+/// module (path relative to src, e.g. schedulers::local_search) => max level of the messages of this module and its submodules
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogFilter(Vec<(String, LogLevel)>);
+
+impl FromStr for LogFilter {
+    type Err = String;
+
+    /// format: "module=level,module=level,..."
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut module_levels = vec![];
+        for part in input.split(',').filter(|part| !part.is_empty()) {
+            let (module, level) = part.split_once('=').ok_or(format!("invalid log filter (expected module=level): {part}"))?;
+            module_levels.push((module.to_string(), LogLevel::from_str(level, true)?));
+        }
+        Ok(LogFilter(module_levels))
+    }
+}
+
+struct Logger {
+    level: LogLevel,
+    filter: LogFilter,
+    format: LogFormat,
+    writer: Mutex<Box<dyn Write + Send>>,
+}
+
+/// sets the global logger (can only be done once, messages before use the default: debug, stdout, text)
+pub fn init_logger(level: LogLevel, filter: LogFilter, format: LogFormat, target: &LogTarget) {
+    let writer: Box<dyn Write + Send> = match target {
+        LogTarget::Stdout => Box::new(stdout()),
+        LogTarget::Stderr => Box::new(stderr()),
+        LogTarget::File(path) => Box::new(LineWriter::new(File::create(path).unwrap_or_else(|e| panic!("couldn't create {:?}: {}", path, e)))),
+    };
+    if LOGGER.set(Logger { level, filter, format, writer: Mutex::new(writer) }).is_err() {
+        panic!("the logger is already initialized");
+    }
+}
+
+///used for logging (also prints the current thread index if available), the module of the caller is used for the log filter
+#[track_caller]
+pub fn log(level: LogLevel, message: String, currently_running_algo: Option<Algorithm>) {
+    log_for_module(&get_module(Location::caller().file()), level, message, currently_running_algo);
+}
+
+///like log, but for messages on behalf of another module (e.g. the events get logged under the module emitting them)
+pub fn log_for_module(module: &str, level: LogLevel, message: String, currently_running_algo: Option<Algorithm>) {
+    let logger = LOGGER.get_or_init(|| Logger { level: LogLevel::Debug, filter: LogFilter(vec![]), format: LogFormat::Text, writer: Mutex::new(Box::new(stdout())) });
+    //the most specific matching module filter wins:
+    let max_level = logger.filter.0.iter()
+        .filter(|(filter_module, _)| module == *filter_module || module.starts_with(&format!("{filter_module}::")))
+        .max_by_key(|(filter_module, _)| filter_module.len())
+        .map_or(logger.level, |(_, level)| *level);
+    if level > max_level {
+        return;
+    }
+
+    let thread_opt = current_thread_index();
+    let line = match logger.format {
+        LogFormat::Text => {
+            let thread = match thread_opt {
+                None => { String::new() }
+                Some(t) => { format!("thread_{}", t) }
+            };
+            let algo = match currently_running_algo {
+                None => { String::new() }
+                Some(a) => { format!("_{:?}", a) }
+            };
+            format!("{}{}: {}", thread, algo, message)
+        }
+        LogFormat::Json => {
+            let thread = thread_opt.map_or(String::from("null"), |t| t.to_string());
+            let algo = currently_running_algo.map_or(String::from("null"), |a| format!("\"{:?}\"", a));
+            format!("{{\"timestamp\":\"{}\",\"level\":\"{}\",\"module\":\"{}\",\"thread\":{},\"algorithm\":{},\"message\":\"{}\"}}", Local::now().format("%Y-%m-%dT%H:%M:%S%.3f%:z"), format!("{:?}", level).to_lowercase(), module, thread, algo, escape_json(&message))
+        }
+    };
+    if let Err(e) = writeln!(logger.writer.lock().unwrap(), "{}", line) {
+        eprintln!("Couldn't write the log: {}", e);
+    }
+}
+
+/// src/schedulers/local_search/swapper.rs => schedulers::local_search::swapper
+fn get_module(file: &str) -> String {
+    let path = file.strip_prefix("src/").unwrap_or(file).trim_end_matches(".rs").trim_end_matches("/mod");
+    path.replace(['/', '\\'], "::")
+}
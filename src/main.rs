@@ -4,50 +4,83 @@ use std::hash::Hash;
 use std::path::PathBuf;
 use std::process::exit;
 use std::string::String;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, mpsc, Mutex};
 use std::thread::sleep;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use atoi::atoi;
 use clap::{arg, Parser, ValueEnum};
 use enum_map::{Enum, enum_map, EnumMap};
-
-use crate::Algorithm::{BF, FF, LPT, RF, RR, Swap};
+use signal_hook::consts::{SIGINT, SIGTERM};
+use signal_hook::iterator::Signals;
+
+use crate::Algorithm::{AntColony, BeamSearch, BF, FF, Genetic, HyperHeuristic, IG, LNS, LPT, PathRelinking, RF, RR, Swap, VNS};
+use crate::cancellation::cancellation_token::{CancellationToken, StopReason};
+use crate::checkpoint::checkpoint_file::Checkpoint;
+use crate::events::event::Event;
+use crate::events::event_bus::EventBus;
+use crate::events::sinks::{CsvTrajectorySink, JsonLinesSink, LogSink};
 use crate::global_bounds::bounds::Bounds;
 use crate::good_solutions::good_solutions::GoodSolutions;
+use crate::good_solutions::persistence::{good_solutions_to_string, load_good_solutions, save_good_solutions};
+use crate::good_solutions::replacement_policy::ReplacementPolicy;
 use crate::input::{get_input, MyRng, RngSeed};
 use crate::input::input::Input;
-use crate::output::log;
+use crate::logging::logger::{init_logger, log, LogFilter, LogFormat, LogLevel, LogTarget};
+use crate::output::{get_directory_name, output_solution, OutputFormat, OutputOptions};
+use crate::schedulers::list_schedulers::beam_search_scheduler::{BeamSearchConfig, BeamSearchScheduler};
 use crate::schedulers::list_schedulers::bf_scheduler::BFScheduler;
 use crate::schedulers::list_schedulers::ff_scheduler::FFScheduler;
 use crate::schedulers::list_schedulers::lpt_scheduler::LPTScheduler;
 use crate::schedulers::list_schedulers::rf_scheduler::{RFConfig, RFScheduler};
 use crate::schedulers::list_schedulers::rr_scheduler::RRScheduler;
+use crate::schedulers::local_search::hyper_heuristic::{HyperHeuristicConfig, HyperHeuristicScheduler};
+use crate::schedulers::local_search::iterated_greedy::{IGConfig, IGScheduler};
+use crate::schedulers::local_search::lns::{LNSConfig, LNSScheduler};
 use crate::schedulers::local_search::swapper::{SwapConfig, Swapper};
+use crate::schedulers::local_search::vns::{VNSConfig, VNSScheduler};
+use crate::schedulers::metaheuristics::ant_colony::{AntColonyConfig, AntColonyScheduler};
+use crate::schedulers::metaheuristics::genetic::{GeneticConfig, GeneticScheduler};
+use crate::schedulers::metaheuristics::path_relinking::{PathRelinkingConfig, PathRelinkingScheduler};
 use crate::schedulers::scheduler::Scheduler;
 
+mod cancellation;
+mod checkpoint;
+mod events;
 mod global_bounds;
 mod good_solutions;
 mod input;
+mod logging;
 mod output;
 mod schedulers;
 
+type SchedulerFactory = fn(Arc<Input>, Arc<Bounds>, Arc<Args>, usize, Arc<Mutex<MyRng>>, Arc<Checkpoint>) -> Box<dyn Scheduler + Send>;
+
 /// Framework to solve makespan-minimization problems
 
 fn main() {
     //new algorithms can be added here:
-    let algorithm_map: EnumMap<Algorithm, fn(Arc<Input>, Arc<Bounds>, Arc<Args>, usize, Arc<Mutex<MyRng>>) -> Box<dyn Scheduler + Send>, > = enum_map! {
-        LPT => |input:Arc<Input>,global_bounds: Arc<Bounds>, _args: Arc<Args>, _config_id: usize, _shared_initial_rng: Arc<Mutex<MyRng>>| Box::new(LPTScheduler::new(input,global_bounds)) as Box<dyn Scheduler + Send>,
-        BF=> |input:Arc<Input>,global_bounds: Arc<Bounds>, _args: Arc<Args>, _config_id: usize, _shared_initial_rng: Arc<Mutex<MyRng>>| Box::new(BFScheduler::new(input,global_bounds))as Box<dyn Scheduler + Send>,
-        FF=> |input:Arc<Input>,global_bounds: Arc<Bounds>, _args: Arc<Args>, _config_id: usize, _shared_initial_rng: Arc<Mutex<MyRng>>| Box::new(FFScheduler::new(input,global_bounds))as Box<dyn Scheduler + Send>,
-        RR=> |input:Arc<Input>,global_bounds: Arc<Bounds>, _args: Arc<Args>, _config_id: usize, _shared_initial_rng: Arc<Mutex<MyRng>>| Box::new(RRScheduler::new(input,global_bounds))as Box<dyn Scheduler + Send>,
-        RF=> |input:Arc<Input>,global_bounds: Arc<Bounds>, args: Arc<Args>, config_id: usize, shared_initial_rng: Arc<Mutex<MyRng>>| Box::new(RFScheduler::new(input,global_bounds,&(args.rf_configs[config_id]),shared_initial_rng,None))as Box<dyn Scheduler + Send>,
-        Swap=> |input:Arc<Input>,global_bounds: Arc<Bounds>, args: Arc<Args>, config_id: usize, shared_initial_rng: Arc<Mutex<MyRng>>| Box::new(Swapper::new(input,global_bounds,args.swap_configs[config_id].clone(),shared_initial_rng))as Box<dyn Scheduler + Send>,
+    let algorithm_map: EnumMap<Algorithm, SchedulerFactory> = enum_map! {
+        LPT => |input:Arc<Input>,global_bounds: Arc<Bounds>, _args: Arc<Args>, _config_id: usize, _shared_initial_rng: Arc<Mutex<MyRng>>, _checkpoint: Arc<Checkpoint>| Box::new(LPTScheduler::new(input,global_bounds)) as Box<dyn Scheduler + Send>,
+        BF=> |input:Arc<Input>,global_bounds: Arc<Bounds>, _args: Arc<Args>, _config_id: usize, _shared_initial_rng: Arc<Mutex<MyRng>>, _checkpoint: Arc<Checkpoint>| Box::new(BFScheduler::new(input,global_bounds))as Box<dyn Scheduler + Send>,
+        FF=> |input:Arc<Input>,global_bounds: Arc<Bounds>, _args: Arc<Args>, _config_id: usize, _shared_initial_rng: Arc<Mutex<MyRng>>, _checkpoint: Arc<Checkpoint>| Box::new(FFScheduler::new(input,global_bounds))as Box<dyn Scheduler + Send>,
+        RR=> |input:Arc<Input>,global_bounds: Arc<Bounds>, _args: Arc<Args>, _config_id: usize, _shared_initial_rng: Arc<Mutex<MyRng>>, _checkpoint: Arc<Checkpoint>| Box::new(RRScheduler::new(input,global_bounds))as Box<dyn Scheduler + Send>,
+        RF=> |input:Arc<Input>,global_bounds: Arc<Bounds>, args: Arc<Args>, config_id: usize, shared_initial_rng: Arc<Mutex<MyRng>>, _checkpoint: Arc<Checkpoint>| Box::new(RFScheduler::new(input,global_bounds,&(args.rf_configs[config_id]),shared_initial_rng,None))as Box<dyn Scheduler + Send>,
+        Swap=> |input:Arc<Input>,global_bounds: Arc<Bounds>, args: Arc<Args>, config_id: usize, shared_initial_rng: Arc<Mutex<MyRng>>, checkpoint: Arc<Checkpoint>| Box::new(Swapper::new(input,global_bounds,args.swap_configs[config_id].clone(),shared_initial_rng).with_checkpoint(checkpoint,config_id))as Box<dyn Scheduler + Send>,
+        VNS=> |input:Arc<Input>,global_bounds: Arc<Bounds>, args: Arc<Args>, config_id: usize, shared_initial_rng: Arc<Mutex<MyRng>>, _checkpoint: Arc<Checkpoint>| Box::new(VNSScheduler::new(input,global_bounds,args.vns_configs[config_id].clone(),shared_initial_rng))as Box<dyn Scheduler + Send>,
+        LNS=> |input:Arc<Input>,global_bounds: Arc<Bounds>, args: Arc<Args>, config_id: usize, shared_initial_rng: Arc<Mutex<MyRng>>, _checkpoint: Arc<Checkpoint>| Box::new(LNSScheduler::new(input,global_bounds,args.lns_configs[config_id].clone(),shared_initial_rng))as Box<dyn Scheduler + Send>,
+        Genetic=> |input:Arc<Input>,global_bounds: Arc<Bounds>, args: Arc<Args>, config_id: usize, shared_initial_rng: Arc<Mutex<MyRng>>, _checkpoint: Arc<Checkpoint>| Box::new(GeneticScheduler::new(input,global_bounds,args.genetic_configs[config_id].clone(),shared_initial_rng))as Box<dyn Scheduler + Send>,
+        HyperHeuristic=> |input:Arc<Input>,global_bounds: Arc<Bounds>, args: Arc<Args>, config_id: usize, shared_initial_rng: Arc<Mutex<MyRng>>, _checkpoint: Arc<Checkpoint>| Box::new(HyperHeuristicScheduler::new(input,global_bounds,args.hyper_heuristic_configs[config_id].clone(),shared_initial_rng))as Box<dyn Scheduler + Send>,
+        PathRelinking=> |input:Arc<Input>,global_bounds: Arc<Bounds>, args: Arc<Args>, config_id: usize, shared_initial_rng: Arc<Mutex<MyRng>>, _checkpoint: Arc<Checkpoint>| Box::new(PathRelinkingScheduler::new(input,global_bounds,args.path_relinking_configs[config_id].clone(),shared_initial_rng))as Box<dyn Scheduler + Send>,
+        IG=> |input:Arc<Input>,global_bounds: Arc<Bounds>, args: Arc<Args>, config_id: usize, shared_initial_rng: Arc<Mutex<MyRng>>, _checkpoint: Arc<Checkpoint>| Box::new(IGScheduler::new(input,global_bounds,args.ig_configs[config_id].clone(),shared_initial_rng))as Box<dyn Scheduler + Send>,
+        BeamSearch=> |input:Arc<Input>,global_bounds: Arc<Bounds>, args: Arc<Args>, config_id: usize, _shared_initial_rng: Arc<Mutex<MyRng>>, _checkpoint: Arc<Checkpoint>| Box::new(BeamSearchScheduler::new(input,global_bounds,args.beam_search_configs[config_id].clone()))as Box<dyn Scheduler + Send>,
+        AntColony=> |input:Arc<Input>,global_bounds: Arc<Bounds>, args: Arc<Args>, config_id: usize, shared_initial_rng: Arc<Mutex<MyRng>>, _checkpoint: Arc<Checkpoint>| Box::new(AntColonyScheduler::new(input,global_bounds,args.ant_colony_configs[config_id].clone(),shared_initial_rng))as Box<dyn Scheduler + Send>,
     };
 
     //start:
     let args = Arc::new(Args::parse());
-    log(format!("\nstart with input {:?}...", args.path), true, true, None);
+    init_logger(args.log_level.unwrap_or(if args.measurement { LogLevel::Info } else { LogLevel::Debug }), args.log_filter.clone(), args.log_format, &args.log_target);
+    log(LogLevel::Info, format!("\nstart with input {:?}...", args.path), None);
 
     let mut algos = vec![]; //das muss man gerade so machen, da das cmd-arg Vec<Algos> keine subcommands zulässt...
     if args.lpt { algos.push(LPT); }
@@ -56,26 +89,65 @@ fn main() {
     if args.rf { algos.push(RF); }
     if args.rr { algos.push(RR); }
     if args.swap { algos.push(Swap); }
-
-    let shared_initial_rng = Arc::new(Mutex::new(args.rng_seed.create_rng()));
-
-    let sorted_input = get_input(&args.path, args.measurement);
+    if args.vns { algos.push(VNS); }
+    if args.lns { algos.push(LNS); }
+    if args.genetic { algos.push(Genetic); }
+    if args.hyper_heuristic { algos.push(HyperHeuristic); }
+    if args.path_relinking { algos.push(PathRelinking); }
+    if args.ig { algos.push(IG); }
+    if args.beam_search { algos.push(BeamSearch); }
+    if args.ant_colony { algos.push(AntColony); }
+
+    let sorted_input = get_input(&args.path);
     let input = sorted_input.get_input();
     let perm = sorted_input.get_permutation();
 
     let thread_pool = rayon::ThreadPoolBuilder::new().num_threads(args.num_threads).build().unwrap();
     let tmp_opt = tmp_get_opt(&args.path);
-    let global_bounds = Arc::new(Bounds::trivial(Arc::clone(&input), tmp_opt));
-    let good_solutions = GoodSolutions::new(args.num_solutions);
+    let (saved_good_solutions, checkpoint, shared_initial_rng, start_time) = match args.resume.as_ref().map(|path| Checkpoint::load(path, &input)) {
+        None => { (args.load_solutions.as_ref().map(|path| load_good_solutions(path, &input)), Checkpoint::default(), args.rng_seed.create_rng(), Instant::now()) }
+        Some(resumed) => {
+            log(LogLevel::Info, format!("resuming the checkpoint after: {:?} sec", resumed.elapsed.as_secs_f64()), None);
+            let now = Instant::now();
+            (Some(resumed.good_solutions), resumed.checkpoint, resumed.shared_initial_rng, now.checked_sub(resumed.elapsed).unwrap_or(now))
+        }
+    };
+    let (checkpoint, shared_initial_rng) = (Arc::new(checkpoint), Arc::new(Mutex::new(shared_initial_rng)));
+    let cancellation_token = Arc::new(CancellationToken::default());
+    let event_bus = Arc::new(EventBus::new(start_time));
+    event_bus.subscribe(Box::new(LogSink));
+    let global_bounds = Arc::new(match &saved_good_solutions {
+        None => { Bounds::trivial(Arc::clone(&input), tmp_opt, Arc::clone(&cancellation_token), Arc::clone(&event_bus)) }
+        Some(saved) => {
+            log(LogLevel::Info, format!("using the saved bounds: UB:{} LB:{} ", saved.upper_bound, saved.lower_bound), None);
+            Bounds::new(saved.upper_bound, saved.lower_bound, tmp_opt, Arc::clone(&cancellation_token), Arc::clone(&event_bus))
+        }
+    });
+    if let Some(path) = &args.trajectory_csv {
+        event_bus.subscribe(Box::new(CsvTrajectorySink::create(path, global_bounds.get_bounds())));
+    }
+    if let Some(path) = &args.events_jsonl {
+        event_bus.subscribe(Box::new(JsonLinesSink::create(path)));
+    }
+    let good_solutions = GoodSolutions::new(args.num_solutions, args.replacement_policy, Arc::clone(&input), Arc::clone(&event_bus), Arc::clone(&cancellation_token));
+    if let Some(saved) = saved_good_solutions {
+        log(LogLevel::Info, format!("loaded {} good solutions (saved run used rng seed {})", saved.solutions.len(), saved.rng_seed), None);
+        for solution in saved.solutions {
+            good_solutions.add_solution(solution);
+        }
+    }
+
+    let saver = Saver { args: Arc::clone(&args), good_solutions: good_solutions.clone(), input: Arc::clone(&input), checkpoint: Arc::clone(&checkpoint), shared_initial_rng: Arc::clone(&shared_initial_rng), start_time };
 
-    let (perm_for_output, args_for_output, good_solutions_for_output, input_for_output) = (Arc::clone(&perm), Arc::clone(&args), good_solutions.clone(), Arc::clone(&input));
+    let (perm_for_output, args_for_output, good_solutions_for_output, input_for_output, bounds_for_output, event_bus_for_output) = (Arc::clone(&perm), Arc::clone(&args), good_solutions.clone(), Arc::clone(&input), Arc::clone(&global_bounds), Arc::clone(&event_bus));
 
     //log(format!("START: {}", Local::now().format("%H:%M:%S%.f")));
-    let start_time = std::time::Instant::now();
     let timeout_duration = Duration::from_secs(args.timeout_after);
+    handle_shutdown_signals(Arc::clone(&cancellation_token));
+    let (schedulers_stopped_sender, schedulers_stopped_receiver) = mpsc::channel();
 
+    let cancellation_token_for_threads = Arc::clone(&cancellation_token);
     thread_pool.spawn(move || {
-        let (perm_for_output, args_for_output, good_solutions_for_output, input_for_output) = (Arc::clone(&perm), Arc::clone(&args), good_solutions.clone(), Arc::clone(&input));
         rayon::scope_fifo(move |s| {
             for algorithm in algos.iter() {
                 let mut config_count: usize = 1;
@@ -83,31 +155,157 @@ fn main() {
                     config_count = args.rf_configs.len();
                 } else if algorithm == &Swap {
                     config_count = args.swap_configs.len();
+                } else if algorithm == &VNS {
+                    config_count = args.vns_configs.len();
+                } else if algorithm == &LNS {
+                    config_count = args.lns_configs.len();
+                } else if algorithm == &Genetic {
+                    config_count = args.genetic_configs.len();
+                } else if algorithm == &HyperHeuristic {
+                    config_count = args.hyper_heuristic_configs.len();
+                } else if algorithm == &PathRelinking {
+                    config_count = args.path_relinking_configs.len();
+                } else if algorithm == &IG {
+                    config_count = args.ig_configs.len();
+                } else if algorithm == &BeamSearch {
+                    config_count = args.beam_search_configs.len();
+                } else if algorithm == &AntColony {
+                    config_count = args.ant_colony_configs.len();
                 }
 
                 for current_config_id in 0..config_count {
                     //clone references to use them in spawned threads:
-                    let (algorithm, good_solutions, input, args, global_bounds, perm, shared_initial_rng) = (algorithm.clone(), good_solutions.clone(), Arc::clone(&input), Arc::clone(&args), Arc::clone(&global_bounds), Arc::clone(&perm), Arc::clone(&shared_initial_rng));
+                    let (algorithm, good_solutions, input, args, global_bounds, perm, shared_initial_rng, checkpoint, event_bus) = (algorithm.clone(), good_solutions.clone(), Arc::clone(&input), Arc::clone(&args), Arc::clone(&global_bounds), Arc::clone(&perm), Arc::clone(&shared_initial_rng), Arc::clone(&checkpoint), Arc::clone(&event_bus));
 
                     s.spawn_fifo(move |_| {
-                        let mut scheduler = algorithm_map[algorithm](input, global_bounds, Arc::clone(&args), current_config_id, shared_initial_rng);
+                        event_bus.emit(Event::AlgorithmStarted { algorithm, config_id: current_config_id });
+                        let mut scheduler = algorithm_map[algorithm](input, global_bounds, Arc::clone(&args), current_config_id, shared_initial_rng, checkpoint);
                         let solution = scheduler.schedule(good_solutions.clone(), args, perm, start_time);
+                        let c_max = if solution.is_satisfiable() { Some(solution.get_data().get_c_max()) } else { None };
                         good_solutions.add_solution(solution);
+                        event_bus.emit(Event::AlgorithmFinished { algorithm, config_id: current_config_id, c_max });
                     });
                 }
             }
         });
-        log(format!("END (all algorithms finished) after: {:?} sec (OPT not necessarily found)", start_time.elapsed().as_secs_f64()), true, args_for_output.measurement, None);
-        good_solutions_for_output.write_output(perm_for_output, args_for_output.write, args_for_output.write_directory_name.clone(), args_for_output.path.file_stem().unwrap().to_str().unwrap(), args_for_output.write_separate_files, args_for_output.measurement, input_for_output.get_jobs(), input_for_output.get_machine_count());
-        exit(0)
+        cancellation_token_for_threads.cancel(StopReason::AllAlgorithmsFinished);
+        let _ = schedulers_stopped_sender.send(());
     });
 
-    while start_time.elapsed() < timeout_duration {
+    let (mut last_solutions_save, mut last_checkpoint_save) = (Instant::now(), Instant::now());
+    while !cancellation_token.is_cancelled() {
         sleep(Duration::from_millis(100)); //hier kann die Genauigkeit angepasst werden
+        if start_time.elapsed() >= timeout_duration {
+            cancellation_token.cancel(StopReason::Timeout);
+        }
+        if args_for_output.save_solutions_interval.is_some_and(|interval| last_solutions_save.elapsed() >= Duration::from_secs(interval)) {
+            saver.save_good_solutions_if_requested(&bounds_for_output);
+            last_solutions_save = Instant::now();
+        }
+        if args_for_output.checkpoint.is_some() && last_checkpoint_save.elapsed() >= Duration::from_secs(args_for_output.checkpoint_interval) {
+            saver.save_checkpoint_if_requested(&bounds_for_output);
+            last_checkpoint_save = Instant::now();
+        }
+    }
+    //schedulers that still wait for solutions stop right away:
+    good_solutions_for_output.notify_cancellation();
+
+    //the only place that writes the final output:
+    let input_file_name = args_for_output.path.file_stem().unwrap().to_str().unwrap();
+    let output_options = OutputOptions {
+        perm: perm_for_output,
+        write: args_for_output.write,
+        directory_name: get_directory_name(args_for_output.write_directory_name.clone(), input_file_name),
+        write_separate_files: args_for_output.write_separate_files,
+        measurement: args_for_output.measurement,
+        output_format: args_for_output.output_format,
+        rng_seed: args_for_output.rng_seed.clone(),
+    };
+    let stop_reason = cancellation_token.get_stop_reason().unwrap();
+    match stop_reason {
+        StopReason::OptimumProven(optimum) => {
+            log(LogLevel::Info, format!("END after: {:?} sec (found OPT solution)", optimum.found_after.as_secs_f64()), optimum.algorithm);
+            good_solutions_for_output.add_solution(optimum.solution.clone());
+            saver.save_if_requested(&bounds_for_output);
+            output_solution(&optimum.solution, &OutputOptions { write_separate_files: optimum.known_opt, ..output_options }, input_for_output.get_jobs(), input_for_output.get_machine_count());
+        }
+        _ => {
+            match stop_reason {
+                StopReason::Signal(signal) => {
+                    //give the schedulers the chance to hand in their solutions:
+                    let _ = schedulers_stopped_receiver.recv_timeout(SHUTDOWN_GRACE_PERIOD);
+                    log(LogLevel::Info, format!("END (received signal {}) after: {:?} sec (OPT not necessarily found)", signal, start_time.elapsed().as_secs_f64()), None);
+                }
+                StopReason::AllAlgorithmsFinished => { log(LogLevel::Info, format!("END (all algorithms finished) after: {:?} sec (OPT not necessarily found)", start_time.elapsed().as_secs_f64()), None); }
+                _ => { log(LogLevel::Info, format!("END (timeout) after: {:?} sec (OPT not necessarily found)", start_time.elapsed().as_secs_f64()), None); }
+            }
+            saver.save_if_requested(&bounds_for_output);
+            good_solutions_for_output.write_output(&output_options, input_for_output.get_jobs(), input_for_output.get_machine_count());
+        }
+    }
+    let (upper_bound, lower_bound) = bounds_for_output.get_bounds();
+    log(LogLevel::Info, format!("final bounds: UB:{} LB:{}", upper_bound, lower_bound), None);
+    event_bus_for_output.flush();
+    match stop_reason {
+        StopReason::Signal(signal) => exit(SIGNAL_EXIT_CODE_OFFSET + signal),
+        _ => exit(0), //stops the schedulers that are still running
+    }
+}
+
+/// how long the main thread waits for the schedulers to stop after SIGINT/SIGTERM before writing the output
+const SHUTDOWN_GRACE_PERIOD: Duration = Duration::from_secs(1);
+/// exit code after SIGINT/SIGTERM = offset + signal number (130/143, like shells report killed processes)
+const SIGNAL_EXIT_CODE_OFFSET: i32 = 128;
+
+/// the first SIGINT/SIGTERM cancels the run (=> graceful shutdown), a signal while the run is already stopping exits immediately
+fn handle_shutdown_signals(cancellation_token: Arc<CancellationToken>) {
+    let mut signals = Signals::new([SIGINT, SIGTERM]).unwrap();
+    std::thread::spawn(move || {
+        for signal in signals.forever() {
+            if !cancellation_token.cancel(StopReason::Signal(signal)) {
+                exit(SIGNAL_EXIT_CODE_OFFSET + signal)
+            }
+            log(LogLevel::Info, format!("received signal {}, stopping the schedulers...", signal), None);
+        }
+    });
+}
+
+/// everything needed to write the good solutions (--save-solutions) and the checkpoint (--checkpoint)
+#[derive(Clone)]
+struct Saver {
+    args: Arc<Args>,
+    good_solutions: GoodSolutions,
+    input: Arc<Input>,
+    checkpoint: Arc<Checkpoint>,
+    shared_initial_rng: Arc<Mutex<MyRng>>,
+    start_time: Instant,
+}
+
+impl Saver {
+    fn save_if_requested(&self, bounds: &Bounds) {
+        self.save_good_solutions_if_requested(bounds);
+        self.save_checkpoint_if_requested(bounds);
     }
 
-    log(format!("END (timeout) after: {:?} sec (OPT not necessarily found)", start_time.elapsed().as_secs_f64()), true, args_for_output.measurement, None);
-    good_solutions_for_output.write_output(perm_for_output, args_for_output.write, args_for_output.write_directory_name.clone(), args_for_output.path.file_stem().unwrap().to_str().unwrap(), args_for_output.write_separate_files, args_for_output.measurement, input_for_output.get_jobs(), input_for_output.get_machine_count());
+    fn save_good_solutions_if_requested(&self, bounds: &Bounds) {
+        if let Some(path) = &self.args.save_solutions {
+            match save_good_solutions(path, &self.good_solutions, bounds, &self.args.rng_seed, &self.input) {
+                Ok(()) => { log(LogLevel::Debug, format!("saved {} good solutions to {:?}", self.good_solutions.get_solution_count(), path), None); }
+                Err(e) => { log(LogLevel::Error, format!("couldn't save the good solutions to {:?}: {}", path, e), None); }
+            }
+        }
+    }
+
+    fn save_checkpoint_if_requested(&self, bounds: &Bounds) {
+        if let Some(path) = &self.args.checkpoint {
+            let shared_initial_rng = self.shared_initial_rng.lock().unwrap().clone();
+            let good_solutions_str = good_solutions_to_string(&self.good_solutions, bounds, &self.args.rng_seed, &self.input);
+            match self.checkpoint.save(path, self.start_time.elapsed(), &shared_initial_rng, &good_solutions_str) {
+                Ok(()) => { log(LogLevel::Debug, format!("saved checkpoint to {:?}", path), None); }
+                Err(e) => { log(LogLevel::Error, format!("couldn't save the checkpoint to {:?}: {}", path, e), None); }
+            }
+        }
+    }
 }
 
 fn tmp_get_opt(path_buf: &PathBuf) -> Option<u32> { //tmp
@@ -164,12 +362,92 @@ struct Args {
     #[arg(long, action)]
     swap: bool,
 
-    /// configurations for running the Swap algo (attention: each config runs indefinitely => using more configs than available threads does not make sense!)
+    /// configurations for running the Swap algo (attention: each config without termination criteria runs indefinitely => using more configs than available threads does not make sense!)
     ///
-    /// (SWAP_CONFIG= "[swap_finding_tactic],[swap_acceptance_rule],[number_of_solutions],[do_restart_after_steps],[restart_after_steps],[restart_possibility],[restart_scaling_factor],[random_restart_possibility],[lambda]" swap_finding_tactic-default=two-job-est-swap, swap_acceptance_rule-default = improvement, number_of_solutions-default=1, number_of_solutions=max=num-threads, other value defaults => see implementation)
+    /// (SWAP_CONFIG= "[swap_finding_tactic],[swap_acceptance_rule],[number_of_solutions],[do_restart_after_steps],[restart_after_steps],[restart_possibility],[restart_scaling_factor],[random_restart_possibility],[lambda],[max_steps],[max_restarts],[stagnation_limit],[time_budget],[target_c_max],[objective],[island_topology],[migration_interval],[migration_policy],[island_size]" swap_finding_tactic-default=two-job-est-swap (others: two-job-random-swap-[fails_until_stop], pair-reoptimization, all-pairs-best-swap, all-pairs-first-swap), swap_acceptance_rule-default = improvement (others: all, decline-by-[p]%-chance, improvement-or-rs-by-[p]%-chance, late-acceptance-[history_length], threshold-accepting-[initial_threshold]-[decrease_percentage]%, great-deluge-[rain_speed in 1/1000 per accepted swap]), number_of_solutions-default=1, number_of_solutions=max=num-threads, termination criteria (max_steps, max_restarts, stagnation_limit = restarts without improvement, time_budget in sec, target_c_max) default=not used, objective = c-max, critical-machines, l2-norm or lexicographic (secondary objectives to escape c_max plateaus, default=c-max), island model (workers restart from their own sub-population of island_size solutions and send migrants to their neighbors every migration_interval restarts): island_topology = ring or full (default=no island model), migration_interval-default=10 (at least 1), migration_policy = best, random or diversity (default=best), island_size-default=10, other value defaults => see implementation)
     #[arg(long, value_name = "SWAP_CONFIG", num_args = 1.., requires = "swap", required_if_eq("swap", "true"))]
     swap_configs: Vec<SwapConfig>,
 
+    /// use VNS (variable neighborhood search) algo
+    #[arg(long, action)]
+    vns: bool,
+
+    /// configurations for running the VNS algo
+    ///
+    /// (VNS_CONFIG= "[neighborhoods],[shaking_k_max],[fails_until_stop]" neighborhoods = '/'-separated ordered list of push, 1-1-swap, 2-1-swap, 2-2-swap, 3-cycle (default=all in this order), shaking_k_max-default=5 (0 => only VND), fails_until_stop-default=100)
+    #[arg(long, value_name = "VNS_CONFIG", num_args = 1.., requires = "vns", required_if_eq("vns", "true"))]
+    vns_configs: Vec<VNSConfig>,
+
+    /// use LNS (large neighborhood search) algo
+    #[arg(long, action)]
+    lns: bool,
+
+    /// configurations for running the LNS algo
+    ///
+    /// (LNS_CONFIG= "[k_min],[k_max],[stagnation_limit],[node_limit],[fails_until_stop]" k = number of destroyed machines (1 <= k_min <= k_max, k_min-default=2, k_max-default=5), stagnation_limit-default=20 (iterations without improvement until k grows), node_limit-default=100000 (branch and bound nodes per repair), fails_until_stop-default=1000)
+    #[arg(long, value_name = "LNS_CONFIG", num_args = 1.., requires = "lns", required_if_eq("lns", "true"))]
+    lns_configs: Vec<LNSConfig>,
+
+    /// use Genetic (memetic algorithm using the good solutions as population) algo
+    #[arg(long, action)]
+    genetic: bool,
+
+    /// configurations for running the Genetic algo
+    ///
+    /// (GENETIC_CONFIG= "[fails_until_stop],[mutation_possibility],[mutation_swaps],[local_search_steps],[local_search_config],[lambda]" fails_until_stop-default=1000 (children without improvement), mutation_possibility-default=0.2, mutation_swaps-default=3, local_search_steps-default=1000, local_search_config="[swap_finding_tactic]/[swap_acceptance_rule]" (defaults => see SWAP_CONFIG), lambda-default=0.5 (parent selection))
+    #[arg(long, value_name = "GENETIC_CONFIG", num_args = 1.., requires = "genetic", required_if_eq("genetic", "true"))]
+    genetic_configs: Vec<GeneticConfig>,
+
+    /// use HyperHeuristic (adaptive selection of swap tactics and swap acceptance rules) algo
+    #[arg(long, action)]
+    hyper_heuristic: bool,
+
+    /// configurations for running the HyperHeuristic algo
+    ///
+    /// (HYPER_HEURISTIC_CONFIG= "[operator_selection],[operators],[steps_per_episode],[fails_until_stop],[lambda]" operator_selection = ucb, ucb-[c] or adaptive-pursuit (default=ucb with c=sqrt(2)), operators = '/'-separated list of [swap_finding_tactic]:[swap_acceptance_rule] (default=mix of all swap tactics, see implementation), steps_per_episode-default=100, fails_until_stop-default=500 (episodes without improvement), lambda-default=0.5 (restart selection))
+    #[arg(long, value_name = "HYPER_HEURISTIC_CONFIG", num_args = 1.., requires = "hyper_heuristic", required_if_eq("hyper_heuristic", "true"))]
+    hyper_heuristic_configs: Vec<HyperHeuristicConfig>,
+
+    /// use PathRelinking (walks between two good solutions) algo
+    #[arg(long, action)]
+    path_relinking: bool,
+
+    /// configurations for running the PathRelinking algo
+    ///
+    /// (PATH_RELINKING_CONFIG= "[fails_until_stop],[direction],[lambda]" fails_until_stop-default=100 (relinked pairs without improvement), direction = forward (better to worse solution), backward or both (default=both), lambda-default=0.5 (solution selection))
+    #[arg(long, value_name = "PATH_RELINKING_CONFIG", num_args = 1.., requires = "path_relinking", required_if_eq("path_relinking", "true"))]
+    path_relinking_configs: Vec<PathRelinkingConfig>,
+
+    /// use IG (iterated greedy) algo
+    #[arg(long, action)]
+    ig: bool,
+
+    /// configurations for running the IG algo
+    ///
+    /// (IG_CONFIG= "[destruction_size],[destruction],[reconstruction],[temperature_factor],[fails_until_stop]" destruction_size-default=4 (removed jobs per iteration), destruction = random or critical (default=critical), reconstruction = lpt or bf (default=lpt), temperature_factor-default=0.5 (Metropolis acceptance), fails_until_stop-default=10000)
+    #[arg(long, value_name = "IG_CONFIG", num_args = 1.., requires = "ig", required_if_eq("ig", "true"))]
+    ig_configs: Vec<IGConfig>,
+
+    /// use BeamSearch (constructive) algo
+    #[arg(long, action)]
+    beam_search: bool,
+
+    /// configurations for running the BeamSearch algo
+    ///
+    /// (BEAM_SEARCH_CONFIG= "[initial_width],[width_growth_factor],[max_width]" initial_width-default=1 (partial schedules kept per assigned job), width_growth_factor-default=2 (min. 2, the search gets repeated with the multiplied width), max_width-default=none (the width grows until the timeout))
+    #[arg(long, value_name = "BEAM_SEARCH_CONFIG", num_args = 1.., requires = "beam_search", required_if_eq("beam_search", "true"))]
+    beam_search_configs: Vec<BeamSearchConfig>,
+
+    /// use AntColony (ant colony optimization) algo
+    #[arg(long, action)]
+    ant_colony: bool,
+
+    /// configurations for running the AntColony algo
+    ///
+    /// (ANT_COLONY_CONFIG= "[ants],[alpha],[beta],[evaporation],[local_search_steps],[local_search_config],[fails_until_stop]" ants-default=20 (at least 1, per iteration, constructed in parallel), alpha-default=1.0 (influence of the job-machine pheromone), beta-default=2.0 (influence of the slack under the upper bound), evaporation-default=0.1 (0 < evaporation < 1), local_search_steps-default=0 (no local improvement of the ants), local_search_config="[swap_finding_tactic]/[swap_acceptance_rule]" (defaults => see SWAP_CONFIG), fails_until_stop-default=100 (iterations without improvement))
+    #[arg(long, value_name = "ANT_COLONY_CONFIG", num_args = 1.., requires = "ant_colony", required_if_eq("ant_colony", "true"))]
+    ant_colony_configs: Vec<AntColonyConfig>,
+
     /// Whether the output should be written in a directory or not
     #[arg(long, action)]
     write: bool,
@@ -182,6 +460,10 @@ struct Args {
     #[arg(long, action, requires = "write")]
     write_separate_files: bool,
 
+    /// text (machine of each job in the sorted order) or json (one object per solution with c_max, machine and start time of each job in the original order, machine loads, algorithms, config, rng seed, bounds and timestamp)
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    output_format: OutputFormat,
+
     /// How many threads to start
     #[arg(long, default_value = "8")]
     num_threads: usize,
@@ -190,6 +472,44 @@ struct Args {
     #[arg(long, default_value = "50")]
     num_solutions: usize,
 
+    /// Which good solutions are kept when more than num_solutions are found
+    ///
+    /// (quality => worst c_max gets removed, quality-and-distance-[min_distance] => additionally rejects solutions closer than min_distance (jobs to move, modulo machine symmetry) to an at least as good one (default=3), hybrid-[diversity_weight] => worst (1-w) * c_max rank + w * diversity rank gets removed (0 <= w <= 1, default=0.5))
+    #[arg(long, default_value_t = ReplacementPolicy::Quality)]
+    replacement_policy: ReplacementPolicy,
+
+    /// File the good solutions (with the bounds and the rng seed) get saved to when the run ends
+    #[arg(long)]
+    save_solutions: Option<PathBuf>,
+
+    /// additionally save the good solutions every x seconds
+    #[arg(long, requires = "save_solutions")]
+    save_solutions_interval: Option<u64>,
+
+    /// File (written by --save-solutions) to restore the good solutions and the bounds from
+    #[arg(long)]
+    load_solutions: Option<PathBuf>,
+
+    /// File the solver state (good solutions, bounds, state of the Swap workers incl. rng's, run time) gets written to periodically and when the run ends
+    #[arg(long)]
+    checkpoint: Option<PathBuf>,
+
+    /// write the checkpoint every x seconds
+    #[arg(long, default_value = "60", requires = "checkpoint")]
+    checkpoint_interval: u64,
+
+    /// File (written by --checkpoint) to continue a run from (use the same algorithm configs; the run time of the checkpoint counts towards timeout_after; deterministic with num_threads=1)
+    #[arg(long, conflicts_with = "load_solutions")]
+    resume: Option<PathBuf>,
+
+    /// File the bound trajectory gets written to (CSV: time,bound,value,algorithm)
+    #[arg(long)]
+    trajectory_csv: Option<PathBuf>,
+
+    /// File all events (new bounds, new good solutions, algorithm started/finished, optimum proven) get written to (one JSON object per line)
+    #[arg(long)]
+    events_jsonl: Option<PathBuf>,
+
     /// execution will be stopped after given amount of seconds
     #[arg(long, default_value = "10")]
     timeout_after: u64,
@@ -200,10 +520,26 @@ struct Args {
     #[arg(long, default_value_t = RngSeed::default())]
     rng_seed: RngSeed,
 
-    /// Whether a measurement will be done or not (changes the amount of logs that are written)
+    /// Whether a measurement will be done or not (preset for the log level: info instead of debug)
     #[arg(long, action)]
     measurement: bool,
 
+    /// Max level of the written log messages: error, warn, info (everything needed for measurements), debug or trace (default: debug, info with --measurement)
+    #[arg(long, value_enum)]
+    log_level: Option<LogLevel>,
+
+    /// Max log level per module path relative to src (incl. submodules, the most specific one wins; the bound changes are logged under global_bounds::bounds), e.g. "schedulers::local_search=trace,global_bounds=warn" (hides the NEW upper_bound/lower_bound lines)
+    #[arg(long, default_value = "")]
+    log_filter: LogFilter,
+
+    /// Where the log messages get written to: stdout, stderr or a file path (the solutions are always written to stdout)
+    #[arg(long, default_value = "stdout")]
+    log_target: LogTarget,
+
+    /// text (thread_{index}_{algorithm}: {message}) or json (one object per line with timestamp, level, module, thread index and algorithm)
+    #[arg(long, value_enum, default_value_t = LogFormat::Text)]
+    log_format: LogFormat,
+
 }
 
 #[derive(Clone, ValueEnum, Debug, Eq, PartialEq, Hash, Enum, Copy)]
@@ -220,6 +556,22 @@ pub enum Algorithm {
     RF,
     /// Swap (local search approach)
     Swap,
+    /// VNS (variable neighborhood search)
+    VNS,
+    /// LNS (large neighborhood search)
+    LNS,
+    /// Genetic (memetic algorithm)
+    Genetic,
+    /// HyperHeuristic (adaptive operator selection for the local search)
+    HyperHeuristic,
+    /// PathRelinking (combination of good solutions)
+    PathRelinking,
+    /// IG (iterated greedy)
+    IG,
+    /// BeamSearch (constructive tree search)
+    BeamSearch,
+    /// AntColony (ant colony optimization)
+    AntColony,
 }
 
 impl fmt::Display for Algorithm {
@@ -27,4 +27,15 @@ impl Data {
         self.machine_jobs.swap_jobs(swap_indices, jobs, keep_sorted);
         self.c_max = self.machine_jobs.get_c_max();
     }
+
+    /// push_indices=(machine_1_index, job_1_index, machine_2_index)
+    pub fn push_job(&mut self, push_indices: (usize, usize, usize), jobs: &[u32]) {
+        self.machine_jobs.push_job(push_indices, jobs);
+        self.c_max = self.machine_jobs.get_c_max();
+    }
+
+    pub fn set_machine_jobs(&mut self, machine_index: usize, job_indices: Vec<usize>, jobs: &[u32]) {
+        self.machine_jobs.set_machine_jobs(machine_index, job_indices, jobs);
+        self.c_max = self.machine_jobs.get_c_max();
+    }
 }
\ No newline at end of file
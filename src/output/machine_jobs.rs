@@ -1,87 +1,153 @@
+use std::collections::BTreeSet;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
 use crate::output::schedule::Schedule;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 ///<(machine0_workload,<machine0_job_numbers...>),...>
-pub struct MachineJobs(Vec<(u32, Vec<usize>)>);
+pub struct MachineJobs {
+    machines: Vec<(u32, Vec<usize>)>,
+    load_index: BTreeSet<(u32, usize)>, //{(machine_workload,machine_index),...} sorted by workload => heaviest/lightest machines in O(log m)
+}
 
 impl MachineJobs {
     pub fn new(machine_jobs: Vec<(u32, Vec<usize>)>) -> Self {
-        Self(machine_jobs)
+        let load_index = machine_jobs.iter().enumerate().map(|(machine_index, (machine_workload, _))| (*machine_workload, machine_index)).collect();
+        Self { machines: machine_jobs, load_index }
     }
 
     pub fn empty(machine_count: usize) -> Self {
-        Self(vec![(0, vec![]); machine_count])
+        Self::new(vec![(0, vec![]); machine_count])
     }
 
     pub fn as_slice(&self) -> &[(u32, Vec<usize>)] {
-        self.0.as_slice()
+        self.machines.as_slice()
     }
-    pub fn as_mut_slice(&mut self) -> &mut [(u32, Vec<usize>)] {
-        self.0.as_mut_slice()
+
+    /// sets the workload of a machine and keeps the load index up to date
+    fn set_machine_workload(&mut self, machine_index: usize, machine_workload: u32) {
+        self.load_index.remove(&(self.machines[machine_index].0, machine_index));
+        self.load_index.insert((machine_workload, machine_index));
+        self.machines[machine_index].0 = machine_workload;
     }
 
     pub fn sort_jobs(&mut self) {
-        for (_, job_indices) in &mut self.0 {
+        for (_, job_indices) in &mut self.machines {
             job_indices.sort_by(|a, b| b.cmp(a));
             //job_indices.sort_by(|&a, &b| jobs[a].cmp(&jobs[b])); //so müsste man eig vergleichen aber die jobs sind ja schon sortiert daher reicht es die indices rückwärts zu sortieren
         }
     }
 
+    pub fn get_machine_count(&self) -> usize {
+        self.machines.len()
+    }
+
     pub fn get_machine_workload(&self, machine_index: usize) -> u32 {
-        self.0[machine_index].0
+        self.machines[machine_index].0
     }
 
     pub fn get_machine_jobs(&self, machine_index: usize) -> &[usize] {
-        self.0[machine_index].1.as_slice()
+        self.machines[machine_index].1.as_slice()
     }
 
     pub fn assign_job(&mut self, job_length: u32, machine_index: usize, job_index: usize) {
-        self.0[machine_index].0 += job_length; //machine_workload aktualisieren
-        self.0[machine_index].1.push(job_index) //job der maschine zuordnen
+        self.set_machine_workload(machine_index, self.machines[machine_index].0 + job_length); //machine_workload aktualisieren
+        self.machines[machine_index].1.push(job_index) //job der maschine zuordnen
+    }
+
+    /// replaces the jobs of a machine
+    pub fn set_machine_jobs(&mut self, machine_index: usize, job_indices: Vec<usize>, jobs: &[u32]) {
+        self.set_machine_workload(machine_index, job_indices.iter().map(|&job_index| jobs[job_index]).sum());
+        self.machines[machine_index].1 = job_indices;
     }
 
+    /// O(log m)
     pub fn get_c_max(&self) -> u32 {
-        let mut c_max = 0;
-        for &(machine_workload, _) in self.0.iter() {
-            if machine_workload > c_max {
-                c_max = machine_workload;
-            }
+        match self.load_index.last() {
+            None => { 0 }
+            Some(&(c_max, _)) => { c_max }
         }
-        c_max
     }
 
+    /// returns the (ascending) indices of all machines with the given workload
     pub fn get_machines_with_workload(&self, workload: u32) -> Vec<usize> {
-        let mut heaviest_machines = vec![];
-        for i in 0..self.0.len() {
-            if self.0[i].0 == workload {
-                heaviest_machines.push(i);
-            }
+        self.load_index.range((workload, 0)..=(workload, usize::MAX)).map(|&(_, machine_index)| machine_index).collect()
+    }
+
+    /// number of machines with the given workload except the given ones (without collecting them)
+    pub fn count_machines_with_workload_without(&self, workload: u32, excluded_machines: &[usize]) -> usize {
+        self.load_index.range((workload, 0)..=(workload, usize::MAX)).filter(|(_, machine_index)| !excluded_machines.contains(machine_index)).count()
+    }
+
+    /// max workload of all machines except the given ones (0 if there is none)
+    pub fn get_max_workload_without(&self, excluded_machines: &[usize]) -> u32 {
+        match self.load_index.iter().rev().find(|(_, machine_index)| !excluded_machines.contains(machine_index)) {
+            None => { 0 }
+            Some(&(machine_workload, _)) => { machine_workload }
         }
-        heaviest_machines
     }
 
+    /// sum of the squared machine workloads (squared L2 norm of the loads)
+    pub fn get_squared_workload_sum(&self) -> u64 {
+        self.machines.iter().map(|&(machine_workload, _)| machine_workload as u64 * machine_workload as u64).sum()
+    }
+
+    /// O(log m) (smallest index if there are multiple lightest machines)
     pub fn get_lightest_machine_index(&self) -> usize {
-        let mut lightest_index = 0;
-        for i in 0..self.0.len() {
-            if self.0[i].0 < self.0[lightest_index].0 {
-                lightest_index = i;
-            }
-        }
-        lightest_index
+        self.load_index.first().unwrap().1
     }
 
+    /// O(log m) (smallest index if there are multiple heaviest machines)
     pub fn get_heaviest_machine_index(&self) -> usize {
-        let mut heaviest_index = 0;
-        for i in 0..self.0.len() {
-            if self.0[i].0 > self.0[heaviest_index].0 {
-                heaviest_index = i;
-            }
-        }
-        heaviest_index
+        self.load_index.range((self.get_c_max(), 0)..).next().unwrap().1
+    }
+
+    /// optimal matching of the machines of both solutions (maximizes the number of job lengths that stay on matched machines, hungarian algorithm O(m³))
+    /// returns the machine of other that is matched to each machine of self
+    pub fn get_machine_matching(&self, other: &MachineJobs, jobs: &[u32]) -> Vec<usize> {
+        hungarian(&self.get_matching_costs(other, jobs))
+    }
+
+    /// number of jobs that have to be moved to transform self into other (job lengths are compared as multisets, machines are matched optimally)
+    pub fn distance(&self, other: &MachineJobs, jobs: &[u32]) -> usize {
+        let cost = self.get_matching_costs(other, jobs);
+        let common_jobs: i64 = hungarian(&cost).iter().enumerate().map(|(machine_index, &other_machine_index)| -cost[machine_index][other_machine_index]).sum();
+        jobs.len() - common_jobs as usize
+    }
+
+    /// ascending job lengths of each machine
+    fn get_sorted_job_lengths(&self, jobs: &[u32]) -> Vec<Vec<u32>> {
+        self.machines.iter().map(|(_, job_indices)| {
+            let mut job_lengths: Vec<u32> = job_indices.iter().map(|&job_index| jobs[job_index]).collect();
+            job_lengths.sort();
+            job_lengths
+        }).collect()
+    }
+
+    /// sorted job lengths of each machine, machines sorted by them => equal for schedules that only differ by machine relabeling
+    /// or by swapping jobs with equal length, O(n log n)
+    pub fn get_canonical_form(&self, jobs: &[u32]) -> Vec<Vec<u32>> {
+        let mut canonical_form = self.get_sorted_job_lengths(jobs);
+        canonical_form.sort();
+        canonical_form
+    }
+
+    /// hash of a canonical form (see get_canonical_form), equal hashes don't imply equal canonical forms
+    pub fn hash_canonical_form(canonical_form: &[Vec<u32>]) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        canonical_form.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// cost[a][b] = -(number of equal job lengths on machine a of self and machine b of other)
+    fn get_matching_costs(&self, other: &MachineJobs, jobs: &[u32]) -> Vec<Vec<i64>> {
+        let (self_job_lengths, other_job_lengths) = (self.get_sorted_job_lengths(jobs), other.get_sorted_job_lengths(jobs));
+        self_job_lengths.iter().map(|a| other_job_lengths.iter().map(|b| -(common_job_length_count(a, b) as i64)).collect()).collect()
     }
 
     pub fn calculate_schedule(&self, jobs: &[u32]) -> Schedule {
-        Schedule::from_machine_jobs(&self, jobs, self.0.len())
+        Schedule::from_machine_jobs(self, jobs, self.machines.len())
     }
 
     /// job indices on the current machine - NOT general job index
@@ -93,15 +159,15 @@ impl MachineJobs {
         if job_2_index_on_machine == -1 { //push:
             self.push_job((machine_1_index, job_1_index_on_machine, machine_2_index), jobs)
         } else { //swap:
-            let job_1_index = self.0[machine_1_index].1[job_1_index_on_machine];
-            let job_2_index = self.0[machine_2_index].1[job_2_index_on_machine as usize];
-            self.0[machine_1_index].0 = self.0[machine_1_index].0 + jobs[job_2_index] - jobs[job_1_index];
-            self.0[machine_2_index].0 = self.0[machine_2_index].0 + jobs[job_1_index] - jobs[job_2_index];
-            self.0[machine_1_index].1[job_1_index_on_machine] = job_2_index;
-            self.0[machine_2_index].1[job_2_index_on_machine as usize] = job_1_index;
+            let job_1_index = self.machines[machine_1_index].1[job_1_index_on_machine];
+            let job_2_index = self.machines[machine_2_index].1[job_2_index_on_machine as usize];
+            self.set_machine_workload(machine_1_index, self.machines[machine_1_index].0 + jobs[job_2_index] - jobs[job_1_index]);
+            self.set_machine_workload(machine_2_index, self.machines[machine_2_index].0 + jobs[job_1_index] - jobs[job_2_index]);
+            self.machines[machine_1_index].1[job_1_index_on_machine] = job_2_index;
+            self.machines[machine_2_index].1[job_2_index_on_machine as usize] = job_1_index;
             if keep_sorted {
-                self.0[machine_1_index].1.sort_by(|a, b| b.cmp(a));
-                self.0[machine_2_index].1.sort_by(|a, b| b.cmp(a));
+                self.machines[machine_1_index].1.sort_by(|a, b| b.cmp(a));
+                self.machines[machine_2_index].1.sort_by(|a, b| b.cmp(a));
             }
         }
     }
@@ -111,10 +177,268 @@ impl MachineJobs {
     /// push_indices: (m1, j1, m2)
     pub fn push_job(&mut self, push_indices: (usize, usize, usize), jobs: &[u32]) {
         let (machine_1_index, job_1_index_on_machine, machine_2_index) = push_indices;
-        let job_1_index = self.0[machine_1_index].1[job_1_index_on_machine];
-        self.0[machine_1_index].0 = self.0[machine_1_index].0 - jobs[job_1_index];
-        self.0[machine_2_index].0 = self.0[machine_2_index].0 + jobs[job_1_index];
-        self.0[machine_1_index].1.remove(job_1_index_on_machine);
-        self.0[machine_2_index].1.push(job_1_index);
+        let job_1_index = self.remove_job(machine_1_index, job_1_index_on_machine, jobs);
+        self.assign_job(jobs[job_1_index], machine_2_index, job_1_index);
+    }
+
+    /// removes a job from a machine and returns its general job index
+    /// job index on the current machine - NOT general job index
+    pub fn remove_job(&mut self, machine_index: usize, job_index_on_machine: usize, jobs: &[u32]) -> usize {
+        let job_index = self.machines[machine_index].1.remove(job_index_on_machine);
+        self.set_machine_workload(machine_index, self.machines[machine_index].0 - jobs[job_index]);
+        job_index
+    }
+}
+
+/// size of the multiset intersection of two ascending sorted lists of job lengths
+fn common_job_length_count(a: &[u32], b: &[u32]) -> usize {
+    let (mut i, mut j, mut count) = (0, 0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] < b[j] {
+            i += 1;
+        } else if a[i] > b[j] {
+            j += 1;
+        } else {
+            count += 1;
+            i += 1;
+            j += 1;
+        }
+    }
+    count
+}
+
+/// min cost perfect matching of a quadratic cost matrix (hungarian algorithm with potentials) O(n³)
+/// returns the column assigned to each row
+fn hungarian(cost: &[Vec<i64>]) -> Vec<usize> {
+    let n = cost.len();
+    //1-based, column 0 is a virtual column used to start the augmenting paths:
+    let (mut u, mut v) = (vec![0i64; n + 1], vec![0i64; n + 1]);
+    let mut row_of_column = vec![0usize; n + 1]; //0 => unassigned
+    let mut way = vec![0usize; n + 1];
+
+    for row in 1..=n {
+        row_of_column[0] = row;
+        let mut column = 0;
+        let mut min_v = vec![i64::MAX; n + 1];
+        let mut used = vec![false; n + 1];
+        loop {
+            used[column] = true;
+            let current_row = row_of_column[column];
+            let (mut delta, mut next_column) = (i64::MAX, 0);
+            for j in 1..=n {
+                if !used[j] {
+                    let reduced_cost = cost[current_row - 1][j - 1] - u[current_row] - v[j];
+                    if reduced_cost < min_v[j] {
+                        min_v[j] = reduced_cost;
+                        way[j] = column;
+                    }
+                    if min_v[j] < delta {
+                        delta = min_v[j];
+                        next_column = j;
+                    }
+                }
+            }
+            for j in 0..=n {
+                if used[j] {
+                    u[row_of_column[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    min_v[j] -= delta;
+                }
+            }
+            column = next_column;
+            if row_of_column[column] == 0 {
+                break;
+            }
+        }
+        //augment along the found path:
+        while column != 0 {
+            let previous_column = way[column];
+            row_of_column[column] = row_of_column[previous_column];
+            column = previous_column;
+        }
+    }
+
+    let mut assignment = vec![0; n];
+    for j in 1..=n {
+        assignment[row_of_column[j] - 1] = j - 1;
+    }
+    assignment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// jobs sorted descending like the parsed input
+    const JOBS: [u32; 6] = [9, 7, 5, 5, 3, 1];
+
+    fn machine_jobs(machines: &[&[usize]]) -> MachineJobs {
+        MachineJobs::new(machines.iter().map(|job_indices| (job_indices.iter().map(|&job_index| JOBS[job_index]).sum(), job_indices.to_vec())).collect())
+    }
+
+    fn assignment_cost(cost: &[Vec<i64>], assignment: &[usize]) -> i64 {
+        assignment.iter().enumerate().map(|(row, &column)| cost[row][column]).sum()
+    }
+
+    /// min cost of all permutations
+    fn brute_force_min_cost(cost: &[Vec<i64>]) -> i64 {
+        fn permute(cost: &[Vec<i64>], row: usize, used: &mut Vec<bool>) -> i64 {
+            if row == cost.len() {
+                return 0;
+            }
+            let mut best = i64::MAX;
+            for column in 0..cost.len() {
+                if !used[column] {
+                    used[column] = true;
+                    best = best.min(cost[row][column] + permute(cost, row + 1, used));
+                    used[column] = false;
+                }
+            }
+            best
+        }
+        permute(cost, 0, &mut vec![false; cost.len()])
+    }
+
+    #[test]
+    fn hungarian_returns_an_optimal_permutation() {
+        let costs = vec![
+            vec![vec![4, 1, 3], vec![2, 0, 5], vec![3, 2, 2]],
+            vec![vec![-3, 0, -1, 0], vec![0, -2, 0, -4], vec![-1, -1, 0, 0], vec![0, -5, -2, -1]],
+            vec![vec![7]],
+            vec![vec![1, 1], vec![1, 1]],
+        ];
+        for cost in costs {
+            let assignment = hungarian(&cost);
+            let mut columns = assignment.clone();
+            columns.sort();
+            assert_eq!(columns, (0..cost.len()).collect::<Vec<usize>>());
+            assert_eq!(assignment_cost(&cost, &assignment), brute_force_min_cost(&cost));
+        }
+    }
+
+    #[test]
+    fn distance_ignores_machine_relabeling_and_equal_jobs() {
+        let a = machine_jobs(&[&[0, 5], &[1, 2], &[3, 4]]);
+        let relabeled = machine_jobs(&[&[3, 4], &[0, 5], &[1, 2]]);
+        let equal_jobs_swapped = machine_jobs(&[&[0, 5], &[1, 3], &[2, 4]]);
+        assert_eq!(a.distance(&a, &JOBS), 0);
+        assert_eq!(a.distance(&relabeled, &JOBS), 0);
+        assert_eq!(a.distance(&equal_jobs_swapped, &JOBS), 0);
+    }
+
+    #[test]
+    fn distance_counts_moved_jobs() {
+        let a = machine_jobs(&[&[0, 5], &[1, 2], &[3, 4]]);
+        let one_moved = machine_jobs(&[&[0], &[1, 2, 5], &[3, 4]]);
+        let two_swapped = machine_jobs(&[&[0, 4], &[1, 2], &[3, 5]]);
+        assert_eq!(a.distance(&one_moved, &JOBS), 1);
+        assert_eq!(one_moved.distance(&a, &JOBS), 1);
+        assert_eq!(a.distance(&two_swapped, &JOBS), 2);
+    }
+
+    #[test]
+    fn canonical_form_ignores_machine_relabeling() {
+        let a = machine_jobs(&[&[0, 5], &[1, 2], &[3, 4]]);
+        let relabeled = machine_jobs(&[&[3, 4], &[0, 5], &[1, 2]]);
+        let reordered_jobs = machine_jobs(&[&[5, 0], &[2, 1], &[4, 3]]);
+        assert_eq!(a.get_canonical_form(&JOBS), relabeled.get_canonical_form(&JOBS));
+        assert_eq!(a.get_canonical_form(&JOBS), reordered_jobs.get_canonical_form(&JOBS));
+    }
+
+    #[test]
+    fn canonical_form_ignores_swapped_jobs_with_equal_length() {
+        //jobs 2 and 3 both have length 5:
+        let a = machine_jobs(&[&[0, 5], &[1, 2], &[3, 4]]);
+        let equal_jobs_swapped = machine_jobs(&[&[0, 5], &[1, 3], &[2, 4]]);
+        assert_eq!(a.get_canonical_form(&JOBS), equal_jobs_swapped.get_canonical_form(&JOBS));
+        assert_eq!(MachineJobs::hash_canonical_form(&a.get_canonical_form(&JOBS)), MachineJobs::hash_canonical_form(&equal_jobs_swapped.get_canonical_form(&JOBS)));
+    }
+
+    #[test]
+    fn canonical_form_differs_for_different_schedules() {
+        let a = machine_jobs(&[&[0, 5], &[1, 2], &[3, 4]]);
+        let other_jobs_swapped = machine_jobs(&[&[0, 4], &[1, 2], &[3, 5]]);
+        let one_moved = machine_jobs(&[&[0], &[1, 2, 5], &[3, 4]]);
+        assert_ne!(a.get_canonical_form(&JOBS), other_jobs_swapped.get_canonical_form(&JOBS));
+        assert_ne!(a.get_canonical_form(&JOBS), one_moved.get_canonical_form(&JOBS));
+    }
+
+    /// compares the load_index queries with a full scan over the machines
+    fn assert_load_index_consistent(machine_jobs: &MachineJobs) {
+        let workloads: Vec<u32> = machine_jobs.as_slice().iter().map(|(machine_workload, _)| *machine_workload).collect();
+        for (machine_index, (machine_workload, job_indices)) in machine_jobs.as_slice().iter().enumerate() {
+            assert_eq!(*machine_workload, job_indices.iter().map(|&job_index| JOBS[job_index]).sum::<u32>(), "workload of machine {machine_index}");
+        }
+        let c_max = *workloads.iter().max().unwrap();
+        let min_workload = *workloads.iter().min().unwrap();
+        assert_eq!(machine_jobs.get_c_max(), c_max);
+        assert_eq!(machine_jobs.get_heaviest_machine_index(), workloads.iter().position(|&workload| workload == c_max).unwrap());
+        assert_eq!(machine_jobs.get_lightest_machine_index(), workloads.iter().position(|&workload| workload == min_workload).unwrap());
+        for &workload in workloads.iter() {
+            let expected: Vec<usize> = (0..workloads.len()).filter(|&machine_index| workloads[machine_index] == workload).collect();
+            assert_eq!(machine_jobs.get_machines_with_workload(workload), expected);
+        }
+        assert!(machine_jobs.get_machines_with_workload(c_max + 1).is_empty());
+    }
+
+    #[test]
+    fn load_index_stays_consistent_after_every_mutation() {
+        let mut machine_jobs = MachineJobs::empty(3);
+        assert_load_index_consistent(&machine_jobs);
+        for (job_index, machine_index) in [(0, 0), (1, 1), (2, 2), (3, 2), (4, 1), (5, 0)] {
+            machine_jobs.assign_job(JOBS[job_index], machine_index, job_index);
+            assert_load_index_consistent(&machine_jobs);
+        }
+        //workloads 10, 10, 10:
+        machine_jobs.swap_jobs((0, 0, 1, 0), &JOBS, false);
+        assert_load_index_consistent(&machine_jobs);
+        machine_jobs.swap_jobs((1, 1, 2, 0), &JOBS, true);
+        assert_load_index_consistent(&machine_jobs);
+        machine_jobs.swap_jobs((2, 0, 0, -1), &JOBS, false);
+        assert_load_index_consistent(&machine_jobs);
+        machine_jobs.push_job((0, 0, 1), &JOBS);
+        assert_load_index_consistent(&machine_jobs);
+        let removed_job_index = machine_jobs.remove_job(1, 0, &JOBS);
+        assert_load_index_consistent(&machine_jobs);
+        machine_jobs.assign_job(JOBS[removed_job_index], 2, removed_job_index);
+        assert_load_index_consistent(&machine_jobs);
+        machine_jobs.set_machine_jobs(0, vec![], &JOBS);
+        assert_load_index_consistent(&machine_jobs);
+        machine_jobs.set_machine_jobs(0, vec![0, 1, 2, 3, 4, 5], &JOBS);
+        machine_jobs.set_machine_jobs(1, vec![], &JOBS);
+        machine_jobs.set_machine_jobs(2, vec![], &JOBS);
+        assert_load_index_consistent(&machine_jobs);
+    }
+
+    #[test]
+    fn load_index_resolves_ties_to_the_smallest_machine_index() {
+        //workloads 9, 9, 7, 5:
+        let mut machine_jobs = machine_jobs(&[&[0], &[2, 4, 5], &[1], &[3]]);
+        assert_load_index_consistent(&machine_jobs);
+        assert_eq!(machine_jobs.get_heaviest_machine_index(), 0);
+        assert_eq!(machine_jobs.get_machines_with_workload(9), vec![0, 1]);
+        //swap 9 and 5 => workloads 5, 9, 7, 9:
+        machine_jobs.swap_jobs((0, 0, 3, 0), &JOBS, false);
+        assert_load_index_consistent(&machine_jobs);
+        assert_eq!(machine_jobs.get_heaviest_machine_index(), 1);
+        assert_eq!(machine_jobs.get_machines_with_workload(9), vec![1, 3]);
+        assert_eq!(machine_jobs.get_lightest_machine_index(), 0);
+        //push 3 from machine 1 to machine 0 => workloads 8, 6, 7, 9:
+        machine_jobs.push_job((1, 1, 0), &JOBS);
+        assert_load_index_consistent(&machine_jobs);
+        assert_eq!(machine_jobs.get_heaviest_machine_index(), 3);
+        //remove 1 from machine 1 => workloads 8, 5, 7, 9, then equal lightest machines:
+        machine_jobs.remove_job(1, 1, &JOBS);
+        machine_jobs.set_machine_jobs(2, vec![3], &JOBS);
+        assert_load_index_consistent(&machine_jobs);
+        assert_eq!(machine_jobs.get_lightest_machine_index(), 1);
+        assert_eq!(machine_jobs.get_machines_with_workload(5), vec![1, 2]);
+        //empty machines => workloads 0, 5, 5, 0:
+        machine_jobs.set_machine_jobs(0, vec![], &JOBS);
+        machine_jobs.set_machine_jobs(3, vec![], &JOBS);
+        assert_load_index_consistent(&machine_jobs);
+        assert_eq!((machine_jobs.get_heaviest_machine_index(), machine_jobs.get_lightest_machine_index()), (1, 0));
+        assert_eq!(machine_jobs.get_machines_with_workload(0), vec![0, 3]);
     }
-}
\ No newline at end of file
+}
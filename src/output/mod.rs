@@ -5,10 +5,10 @@ use std::path::Path;
 use std::sync::Arc;
 
 use chrono::Local;
+use clap::ValueEnum;
 use permutation::Permutation;
-use rayon::current_thread_index;
 
-use crate::Algorithm;
+use crate::input::RngSeed;
 use crate::output::solution::Solution;
 
 pub mod data;
@@ -16,11 +16,37 @@ pub mod machine_jobs;
 pub mod schedule;
 pub mod solution;
 
-pub fn output_solution(solution: &Solution, perm: Arc<Permutation>, write: bool, directory_name: String, write_separate_files: bool, measurement: bool, jobs: &[u32], machine_count: usize) {
-    if write {
-        let output_string = solution.to_output_string(perm, jobs, machine_count);
+#[derive(Clone, Copy, ValueEnum, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    ///SCHEDULING_SOLUTION c_max machine start_time machine start_time ... 0 (+ algorithms and config)
+    Text,
+    ///one JSON object per solution (see Solution::to_json_string)
+    Json,
+}
+
+/// how and where the solutions get written
+pub struct OutputOptions {
+    pub perm: Arc<Permutation>,
+    pub write: bool,
+    //write to data/{directory_name} instead of stdout
+    pub directory_name: String,
+    pub write_separate_files: bool,
+    pub measurement: bool,
+    //only the best solution gets written
+    pub output_format: OutputFormat,
+    pub rng_seed: RngSeed,
+    //part of the json output
+}
+
+pub fn output_solution(solution: &Solution, options: &OutputOptions, jobs: &[u32], machine_count: usize) {
+    let (output_string, extension, file_name) = match options.output_format {
+        OutputFormat::Text => { (solution.to_output_string(Arc::clone(&options.perm), jobs, machine_count), "txt", "solutions.txt") }
+        OutputFormat::Json => { (solution.to_json_string(Arc::clone(&options.perm), &options.rng_seed, jobs, machine_count) + "\n", "json", "solutions.jsonl") }
+    };
+    let directory_name = &options.directory_name;
+    if options.write {
 
-        if write_separate_files {
+        if options.write_separate_files {
             let mut algorithms_str: String = String::new();
             for algorithm in solution.get_used_algorithms() {
                 algorithms_str.push_str(format!("{:?}_", algorithm).as_str());
@@ -31,21 +57,21 @@ pub fn output_solution(solution: &Solution, perm: Arc<Permutation>, write: bool,
             if !Path::new(&dir).exists() {
                 fs::create_dir(&dir).unwrap();
             }
-            let mut path = format!("data/{0}/{1}.txt", directory_name, original_filename);
+            let mut path = format!("data/{0}/{1}.{2}", directory_name, original_filename, extension);
             let mut i: usize = 0;
             while Path::new(&path).exists() {
                 filename = original_filename.clone();
                 i += 1;
                 filename.push_str(&i.to_string());
-                path = format!("data/{0}/{1}.txt", directory_name, filename);
+                path = format!("data/{0}/{1}.{2}", directory_name, filename, extension);
             }
             let mut file = File::create(path).unwrap();
             file.write_all(output_string.as_bytes()).unwrap();
         } else {
             let dir = format!("data/{}", directory_name);
-            let path = format!("{}{}", dir, "/solutions.txt");
+            let path = format!("{}/{}", dir, file_name);
             if Path::new(&path).exists() {
-                let mut file = OpenOptions::new().write(true).append(true).open(format!("{}{}", dir, "/solutions.txt")).unwrap();
+                let mut file = OpenOptions::new().write(true).append(true).open(&path).unwrap();
                 if let Err(e) = write!(file, "{}", output_string) {
                     eprintln!("Couldn't write to file todo: {}", e);
                 }
@@ -57,7 +83,8 @@ pub fn output_solution(solution: &Solution, perm: Arc<Permutation>, write: bool,
             }
         }
     } else {
-        log(solution.to_output_string(perm, jobs, machine_count) + "\n", true, measurement, None);
+        //solutions always go to stdout (independent of the log target):
+        print!("{}", output_string);
     }
 }
 
@@ -71,19 +98,19 @@ pub fn get_directory_name(directory_name: Option<String>, input_file_name: &str)
     }
 }
 
-///used for logging (also prints the current thread index if available)
-pub fn log(message: String, needed_for_measurement: bool, measurement: bool, currently_running_algo: Option<Algorithm>) {
-    if !measurement || needed_for_measurement {
-        let thread_opt = current_thread_index();
-        let thread = match thread_opt {
-            None => { String::new() }
-            Some(t) => { format!("thread_{}", t) }
-        };
-        let algo = match currently_running_algo {
-            None => { String::new() }
-            Some(a) => { format!("_{:?}", a) }
-        };
-
-        println!("{}{}: {}", thread, algo, message)
+/// escapes a string for the use inside of a JSON string
+pub fn escape_json(str: &str) -> String {
+    let mut escaped = String::with_capacity(str.len());
+    for c in str.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
     }
-}
\ No newline at end of file
+    escaped
+}
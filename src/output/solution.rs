@@ -2,11 +2,14 @@ use std::fmt::Debug;
 use std::sync::Arc;
 use std::time::Instant;
 
+use chrono::{DateTime, Local};
 use permutation::Permutation;
 
 use crate::{Algorithm, Args};
 use crate::global_bounds::bounds::Bounds;
+use crate::input::RngSeed;
 use crate::output::data::Data;
+use crate::output::escape_json;
 use crate::output::machine_jobs::MachineJobs;
 use crate::output::schedule::Schedule;
 
@@ -17,22 +20,39 @@ pub struct Solution {
     used_config: Option<String>,
     //if available
     data: Option<Data>,
+    found: Option<Found>,
+    //set when the solution gets reported to the global bounds (or proves the optimum)
+}
+
+/// bounds and time when the solution was found
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct Found {
+    upper_bound: u32,
+    lower_bound: u32,
+    timestamp: DateTime<Local>,
 }
 
 impl Solution {
     /// creates a new solution, calculates the Schedule and updates the global upper bound
-    pub fn new(used_algorithm: Algorithm, used_config: Option<String>, machine_jobs: MachineJobs, jobs: &[u32], global_bounds: Arc<Bounds>, args: Arc<Args>, perm: Arc<Permutation>, start_time: Instant, machine_count: usize) -> Self {
-        let solution = Self { satisfiable: true, used_algorithms: vec![used_algorithm], used_config, data: Some(Data::new(machine_jobs.get_c_max(), machine_jobs)) };
-        global_bounds.update_upper_bound(solution.get_data().get_c_max(), &solution, args, perm, start_time, Some(used_algorithm), jobs, machine_count);
+    pub fn new(used_algorithm: Algorithm, used_config: Option<String>, machine_jobs: MachineJobs, global_bounds: Arc<Bounds>, args: Arc<Args>, start_time: Instant) -> Self {
+        let c_max = machine_jobs.get_c_max();
+        let mut solution = Self { satisfiable: true, used_algorithms: vec![used_algorithm], used_config, data: Some(Data::new(c_max, machine_jobs)), found: None };
+        global_bounds.update_upper_bound(c_max, &mut solution, args, start_time, Some(used_algorithm));
         solution
     }
 
+    /// recreates a stored solution (e.g. from a good solutions file) without updating the bounds
+    pub fn restore(used_algorithms: Vec<Algorithm>, used_config: Option<String>, machine_jobs: MachineJobs) -> Self {
+        Self { satisfiable: true, used_algorithms, used_config, data: Some(Data::new(machine_jobs.get_c_max(), machine_jobs)), found: None }
+    }
+
     pub fn unsatisfiable(used_algorithm: Algorithm) -> Self {
         Self {
             satisfiable: false,
             used_algorithms: vec![used_algorithm],
             used_config: None,
             data: None,
+            found: None,
         }
     }
 
@@ -52,6 +72,31 @@ impl Solution {
         }
     }
 
+    /// one JSON object (without line break): c_max, machine and start time of each job (original job order), machine loads, used algorithms, config (one entry per config line), rng seed of the run, bounds and time when the solution was found
+    pub fn to_json_string(&self, perm: Arc<Permutation>, rng_seed: &RngSeed, jobs: &[u32], machine_count: usize) -> String {
+        let algorithms: Vec<String> = self.used_algorithms.iter().map(|algorithm| format!("\"{:?}\"", algorithm)).collect();
+        let configs: Vec<String> = self.used_config.as_deref().map_or(vec![], |config| config.lines().map(config_line_to_json).collect());
+        let found = match &self.found {
+            None => { String::from("\"bounds\":null,\"found_at\":null") }
+            Some(found) => { format!("\"bounds\":{{\"upper_bound\":{},\"lower_bound\":{}}},\"found_at\":\"{}\"", found.upper_bound, found.lower_bound, found.timestamp.to_rfc3339()) }
+        };
+        let metadata = format!("\"used_algorithms\":[{}],\"config\":[{}],\"rng_seed\":\"{}\",{}", algorithms.join(","), configs.join(","), rng_seed, found);
+        if !self.satisfiable {
+            return format!("{{\"satisfiable\":false,{}}}", metadata);
+        }
+
+        let mut schedule = Schedule::from_machine_jobs(self.get_data().get_machine_jobs(), jobs, machine_count);
+        schedule.unsort(perm);
+        let schedule: Vec<String> = schedule.as_slice().iter().map(|(machine, start_time)| format!("{{\"machine\":{},\"start_time\":{}}}", machine, start_time)).collect();
+        let machine_loads: Vec<String> = (0..machine_count).map(|machine_index| self.get_data().get_machine_jobs().get_machine_workload(machine_index).to_string()).collect();
+        format!("{{\"satisfiable\":true,\"c_max\":{},\"jobs\":[{}],\"machine_loads\":[{}],{}}}", self.get_data().get_c_max(), schedule.join(","), machine_loads.join(","), metadata)
+    }
+
+    /// remembers the bounds and the current time (see to_json_string)
+    pub fn set_found(&mut self, (upper_bound, lower_bound): (u32, u32)) {
+        self.found = Some(Found { upper_bound, lower_bound, timestamp: Local::now() });
+    }
+
     pub fn is_satisfiable(&self) -> bool {
         self.satisfiable
     }
@@ -64,6 +109,10 @@ impl Solution {
         self.used_algorithms.as_slice()
     }
 
+    pub fn get_used_config(&self) -> Option<&str> {
+        self.used_config.as_deref()
+    }
+
     pub fn add_algorithm(&mut self, algorithm: Algorithm) {
         self.used_algorithms.push(algorithm);
     }
@@ -100,6 +149,19 @@ impl Solution {
     pub fn swap_jobs(&mut self, swap_indices: (usize, usize, usize, i32), jobs: &[u32], keep_sorted: bool) {
         self.get_mut_data().swap_jobs(swap_indices, jobs, keep_sorted);
     }
+
+    pub fn push_job(&mut self, push_indices: (usize, usize, usize), jobs: &[u32]) {
+        self.get_mut_data().push_job(push_indices, jobs);
+    }
+
+    /// equal for solutions that only differ by machine relabeling or by swapping jobs with equal length (see MachineJobs::get_canonical_form)
+    pub fn get_canonical_form(&self, jobs: &[u32]) -> Vec<Vec<u32>> {
+        self.get_data().get_machine_jobs().get_canonical_form(jobs)
+    }
+
+    pub fn set_machine_jobs(&mut self, machine_index: usize, job_indices: Vec<usize>, jobs: &[u32]) {
+        self.get_mut_data().set_machine_jobs(machine_index, job_indices, jobs);
+    }
 }
 
 impl PartialEq for Solution {
@@ -108,3 +170,13 @@ impl PartialEq for Solution {
         self.satisfiable == other.satisfiable && self.data == other.data
     }
 }
+
+/// "NAME: KEY:value; KEY:value" => {"name":"NAME","values":{"KEY":"value",...},"raw":"..."}
+fn config_line_to_json(line: &str) -> String {
+    let (name, values) = match line.split_once(':') {
+        None => { ("", line) }
+        Some((name, values)) => { (name.trim(), values) }
+    };
+    let values: Vec<String> = values.split(';').filter_map(|part| part.split_once(':')).map(|(key, value)| format!("\"{}\":\"{}\"", escape_json(key.trim()), escape_json(value.trim()))).collect();
+    format!("{{\"name\":\"{}\",\"values\":{{{}}},\"raw\":\"{}\"}}", escape_json(name), values.join(","), escape_json(line))
+}
@@ -0,0 +1,330 @@
+use std::cmp::max;
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::string::ParseError;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use permutation::Permutation;
+
+use crate::{Algorithm, Args};
+use crate::Algorithm::BeamSearch;
+use crate::global_bounds::bounds::Bounds;
+use crate::good_solutions::good_solutions::GoodSolutions;
+use crate::input::input::Input;
+use crate::logging::logger::{log, LogLevel};
+use crate::output::machine_jobs::MachineJobs;
+use crate::output::solution::Solution;
+use crate::schedulers::scheduler::Scheduler;
+
+pub struct BeamSearchScheduler {
+    input: Arc<Input>,
+    global_bounds: Arc<Bounds>,
+    config: BeamSearchConfig,
+}
+
+impl Scheduler for BeamSearchScheduler {
+    fn schedule(&mut self, good_solutions: GoodSolutions, args: Arc<Args>, _perm: Arc<Permutation>, start_time: Instant) -> Solution {
+        self.beam_search_with_growing_width(good_solutions, args, start_time)
+    }
+
+    fn get_algorithm(&self) -> Algorithm {
+        BeamSearch
+    }
+}
+
+impl BeamSearchScheduler {
+    pub fn new(input: Arc<Input>, global_bounds: Arc<Bounds>, config: BeamSearchConfig) -> Self {
+        Self { input, global_bounds, config }
+    }
+
+    /// runs the beam search with initial_width, then repeatedly with the width multiplied by width_growth_factor until max_width or the timeout is reached
+    /// every run only keeps partial schedules that can still beat the current upper bound => each found solution is an improvement
+    fn beam_search_with_growing_width(&mut self, good_solutions: GoodSolutions, args: Arc<Args>, start_time: Instant) -> Solution {
+        log(LogLevel::Debug, format!("running {:?} algorithm...", BeamSearch), None);
+
+        let timeout = Duration::from_secs(args.timeout_after);
+        let mut best_solution = Solution::unsatisfiable(BeamSearch);
+        let mut width = self.config.initial_width.max(1);
+
+        loop {
+            let (upper_bound, lower_bound) = self.global_bounds.get_bounds();
+            if upper_bound <= lower_bound {
+                break; //optimum already found
+            }
+
+            log(LogLevel::Debug, format!("running beam search with width {width}..."), Some(BeamSearch));
+            if let Some(machine_jobs) = self.beam_search(width, upper_bound, start_time, timeout) {
+                let config_str = format!("BEAM_SEARCH_CONFIG: INITIAL_WIDTH:{}; WIDTH_GROWTH_FACTOR:{}; MAX_WIDTH:{:?}; WIDTH:{}", self.config.initial_width, self.config.width_growth_factor, self.config.max_width, width);
+                best_solution = Solution::new(BeamSearch, Some(config_str), machine_jobs, Arc::clone(&self.global_bounds), Arc::clone(&args), start_time);
+                good_solutions.add_solution(best_solution.clone());
+            }
+
+            if start_time.elapsed() >= timeout || self.global_bounds.get_cancellation_token().is_cancelled() || self.config.max_width.is_some_and(|max_width| width >= max_width) {
+                break;
+            }
+            width = width.saturating_mul(self.config.width_growth_factor.max(2));
+            if let Some(max_width) = self.config.max_width {
+                width = width.min(max_width);
+            }
+        }
+
+        best_solution
+    }
+
+    /// assigns the jobs (descending by length) one by one, keeping the width best partial schedules of each level
+    /// (partial schedules are stored as sorted load vectors => machine permutations get deduplicated)
+    /// returns the best complete schedule or None if no schedule with c_max < upper_bound was found (or the timeout was reached)
+    fn beam_search(&self, width: usize, upper_bound: u32, start_time: Instant, timeout: Duration) -> Option<MachineJobs> {
+        let jobs = self.input.get_jobs();
+        let machine_count = self.input.get_machine_count();
+        let mut remaining_workload: u64 = jobs.iter().map(|&job_length| job_length as u64).sum();
+
+        //sorted load vectors of the current level:
+        let mut states: Vec<Vec<u32>> = vec![vec![0; machine_count]];
+        //decisions of each level: (index of the parent state, load of the chosen machine before the assignment)
+        let mut decisions: Vec<Vec<(usize, u32)>> = Vec::with_capacity(jobs.len());
+
+        for &job_length in jobs {
+            if start_time.elapsed() >= timeout || self.global_bounds.get_cancellation_token().is_cancelled() {
+                return None;
+            }
+            remaining_workload -= job_length as u64;
+
+            //candidates: (lower bound, c_max of the partial schedule, parent index, chosen load)
+            let mut candidates: Vec<(u32, u32, usize, u32)> = vec![];
+            for (state_index, loads) in states.iter().enumerate() {
+                for position in 0..machine_count {
+                    if position > 0 && loads[position] == loads[position - 1] {
+                        continue; //same child as the previous machine
+                    }
+                    let child = Self::assign(loads, position, job_length);
+                    let c_max = *child.last().unwrap();
+                    let lower_bound = max(c_max, Self::water_level(&child, remaining_workload));
+                    if lower_bound >= upper_bound {
+                        continue; //can't improve the upper bound
+                    }
+                    candidates.push((lower_bound, c_max, state_index, loads[position]));
+                }
+            }
+            if candidates.is_empty() {
+                return None;
+            }
+            candidates.sort_unstable();
+
+            let mut seen: HashSet<Vec<u32>> = HashSet::new(); //sorted load vectors of the next level (equal ones are the same state)
+            let mut next_states = Vec::with_capacity(width.min(candidates.len()));
+            let mut level_decisions = Vec::with_capacity(width.min(candidates.len()));
+            for (_, _, state_index, load) in candidates {
+                if next_states.len() == width {
+                    break;
+                }
+                let loads = &states[state_index];
+                let position = loads.partition_point(|&l| l < load);
+                let child = Self::assign(loads, position, job_length);
+                if !seen.insert(child.clone()) {
+                    continue;
+                }
+                next_states.push(child);
+                level_decisions.push((state_index, load));
+            }
+            states = next_states;
+            decisions.push(level_decisions);
+        }
+
+        //the states are sorted by lower bound => complete schedule with the lowest c_max is the first one
+        Some(Self::replay(&decisions, 0, jobs, machine_count))
+    }
+
+    /// follows the decisions back from state_index of the last level and assigns the jobs to machines with the chosen loads, O(n*log m)
+    fn replay(decisions: &[Vec<(usize, u32)>], state_index: usize, jobs: &[u32], machine_count: usize) -> MachineJobs {
+        let mut chosen_loads = vec![0; jobs.len()];
+        let mut state_index = state_index;
+        for job_index in (0..jobs.len()).rev() {
+            let (parent_index, load) = decisions[job_index][state_index];
+            chosen_loads[job_index] = load;
+            state_index = parent_index;
+        }
+
+        //each parent has the same load multiset as the real machines => a machine with the chosen load exists
+        let mut machine_jobs = MachineJobs::empty(machine_count);
+        for (job_index, load) in chosen_loads.into_iter().enumerate() {
+            let machine_index = machine_jobs.get_machines_with_workload(load)[0];
+            machine_jobs.assign_job(jobs[job_index], machine_index, job_index);
+        }
+        machine_jobs
+    }
+
+    /// adds job_length to the load at position and restores the ascending order, O(m)
+    fn assign(loads: &[u32], position: usize, job_length: u32) -> Vec<u32> {
+        let mut child = loads.to_vec();
+        let new_load = child[position] + job_length;
+        let new_position = position + child[position + 1..].partition_point(|&l| l < new_load);
+        child.copy_within(position + 1..new_position + 1, position);
+        child[new_position] = new_load;
+        child
+    }
+
+    /// min. level c so that the remaining workload fits below c when filling up the least loaded machines (lower bound for c_max), O(m)
+    fn water_level(loads: &[u32], remaining_workload: u64) -> u32 {
+        let mut filled_workload = remaining_workload;
+        for machine_count in 1..=loads.len() {
+            filled_workload += loads[machine_count - 1] as u64;
+            if machine_count == loads.len() || filled_workload <= loads[machine_count] as u64 * machine_count as u64 {
+                return filled_workload.div_ceil(machine_count as u64) as u32;
+            }
+        }
+        0
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BeamSearchConfig {
+    initial_width: usize,
+    width_growth_factor: usize,
+    max_width: Option<usize>,
+    //None => the width grows until the timeout
+}
+
+impl FromStr for BeamSearchConfig {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split(",").collect();
+        Ok(BeamSearchConfig {
+            initial_width: {
+                if !parts[0].is_empty() {
+                    parts[0].parse::<usize>().unwrap()
+                } else {
+                    //default:
+                    1
+                }
+            },
+            width_growth_factor: {
+                if parts.len() > 1 && !parts[1].is_empty() {
+                    parts[1].parse::<usize>().unwrap()
+                } else {
+                    //default:
+                    2
+                }
+            },
+            max_width: {
+                if parts.len() > 2 && !parts[2].is_empty() {
+                    Some(parts[2].parse::<usize>().unwrap())
+                } else {
+                    //default:
+                    None
+                }
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cancellation::cancellation_token::CancellationToken;
+    use crate::events::event_bus::EventBus;
+
+    use super::*;
+
+    /// sorted load vectors of length 3 with loads in 0..5
+    fn sorted_loads() -> Vec<Vec<u32>> {
+        let mut all_loads = vec![];
+        for a in 0..5 {
+            for b in a..5 {
+                for c in b..5 {
+                    all_loads.push(vec![a, b, c]);
+                }
+            }
+        }
+        all_loads
+    }
+
+    fn scheduler(machine_count: usize, jobs: Vec<u32>) -> BeamSearchScheduler {
+        let global_bounds = Arc::new(Bounds::new(u32::MAX, 0, None, Arc::new(CancellationToken::default()), Arc::new(EventBus::new(Instant::now()))));
+        BeamSearchScheduler::new(Arc::new(Input::new(machine_count, jobs)), global_bounds, BeamSearchConfig::from_str("").unwrap())
+    }
+
+    fn beam_search(scheduler: &BeamSearchScheduler, width: usize, upper_bound: u32) -> Option<MachineJobs> {
+        scheduler.beam_search(width, upper_bound, Instant::now(), Duration::from_secs(60))
+    }
+
+    /// each job on the least loaded machine (jobs sorted descending)
+    fn lpt_c_max(machine_count: usize, jobs: &[u32]) -> u32 {
+        let mut machine_jobs = MachineJobs::empty(machine_count);
+        for (job_index, &job_length) in jobs.iter().enumerate() {
+            machine_jobs.assign_job(job_length, machine_jobs.get_lightest_machine_index(), job_index);
+        }
+        machine_jobs.get_c_max()
+    }
+
+    fn assert_complete_schedule(machine_jobs: &MachineJobs, jobs: &[u32]) {
+        let mut job_indices: Vec<usize> = machine_jobs.as_slice().iter().flat_map(|(_, job_indices)| job_indices.iter().copied()).collect();
+        job_indices.sort_unstable();
+        assert_eq!(job_indices, (0..jobs.len()).collect::<Vec<usize>>());
+        for (machine_workload, job_indices) in machine_jobs.as_slice() {
+            assert_eq!(*machine_workload, job_indices.iter().map(|&job_index| jobs[job_index]).sum::<u32>());
+        }
+    }
+
+    #[test]
+    fn assign_keeps_the_loads_sorted() {
+        for loads in sorted_loads() {
+            for position in 0..loads.len() {
+                for job_length in 0..6 {
+                    let mut expected = loads.clone();
+                    expected[position] += job_length;
+                    expected.sort_unstable();
+                    assert_eq!(BeamSearchScheduler::assign(&loads, position, job_length), expected, "loads {loads:?}, position {position}, job_length {job_length}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn water_level_is_the_lowest_level_the_remaining_workload_fits_below() {
+        for loads in sorted_loads() {
+            for remaining_workload in 0..20 {
+                //the level never drops below the least loaded machine:
+                let expected = (loads[0]..).find(|&level| loads.iter().map(|&load| level.saturating_sub(load) as u64).sum::<u64>() >= remaining_workload).unwrap();
+                assert_eq!(BeamSearchScheduler::water_level(&loads, remaining_workload), expected, "loads {loads:?}, remaining_workload {remaining_workload}");
+            }
+        }
+    }
+
+    #[test]
+    fn replay_follows_the_parents_back_to_the_first_level() {
+        let jobs = [5, 4, 3, 3];
+        //levels: [0,5] => [4,5], [0,9] => [5,7], [3,9] => [7,8], [6,9]
+        let decisions = vec![vec![(0, 0)], vec![(0, 0), (0, 5)], vec![(0, 4), (1, 0)], vec![(0, 5), (1, 3)]];
+        assert_eq!(BeamSearchScheduler::replay(&decisions, 0, &jobs, 2).as_slice(), &[(8, vec![0, 3]), (7, vec![1, 2])]);
+        assert_eq!(BeamSearchScheduler::replay(&decisions, 1, &jobs, 2).as_slice(), &[(9, vec![0, 1]), (6, vec![2, 3])]);
+    }
+
+    #[test]
+    fn beam_search_returns_complete_schedules_below_the_upper_bound() {
+        let jobs = vec![19, 17, 17, 13, 11, 11, 8, 7, 5, 5, 3, 2];
+        let scheduler = scheduler(3, jobs.clone());
+        for width in [1, 2, 4, 16, 64] {
+            let machine_jobs = beam_search(&scheduler, width, u32::MAX).unwrap();
+            assert_complete_schedule(&machine_jobs, &jobs);
+            //only improvements get returned:
+            let c_max = machine_jobs.get_c_max();
+            assert!(beam_search(&scheduler, width, c_max).is_none_or(|better| better.get_c_max() < c_max));
+        }
+    }
+
+    #[test]
+    fn width_1_matches_lpt_and_a_larger_width_reaches_the_optimum() {
+        //(jobs, OPT) with LPT > OPT:
+        for (jobs, opt) in [(vec![3, 3, 2, 2, 2], 6), (vec![8, 7, 6, 5, 4], 15)] {
+            let scheduler = scheduler(2, jobs.clone());
+            let lpt_c_max = lpt_c_max(2, &jobs);
+            assert!(lpt_c_max > opt);
+            assert_eq!(beam_search(&scheduler, 1, u32::MAX).unwrap().get_c_max(), lpt_c_max);
+            let machine_jobs = beam_search(&scheduler, 8, u32::MAX).unwrap();
+            assert_complete_schedule(&machine_jobs, &jobs);
+            assert_eq!(machine_jobs.get_c_max(), opt);
+        }
+    }
+}
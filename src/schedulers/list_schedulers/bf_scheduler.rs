@@ -8,7 +8,7 @@ use crate::Algorithm::BF;
 use crate::global_bounds::bounds::Bounds;
 use crate::good_solutions::good_solutions::GoodSolutions;
 use crate::input::input::Input;
-use crate::output::log;
+use crate::logging::logger::{log, LogLevel};
 use crate::output::machine_jobs::MachineJobs;
 use crate::output::solution::Solution;
 use crate::schedulers::scheduler::Scheduler;
@@ -19,8 +19,8 @@ pub struct BFScheduler {
 }
 
 impl Scheduler for BFScheduler {
-    fn schedule(&mut self, _good_solutions: GoodSolutions, args: Arc<Args>, perm: Arc<Permutation>, start_time: Instant) -> Solution {
-        self.best_fit(args, perm, start_time)
+    fn schedule(&mut self, _good_solutions: GoodSolutions, args: Arc<Args>, _perm: Arc<Permutation>, start_time: Instant) -> Solution {
+        self.best_fit(args, start_time)
     }
 
     fn get_algorithm(&self) -> Algorithm {
@@ -34,8 +34,8 @@ impl BFScheduler {
     }
 
     /// Assigns the biggest job to the most loaded machine (that can fit the job) until all jobs are assigned
-    pub fn best_fit(&self, args: Arc<Args>, perm: Arc<Permutation>, start_time: Instant) -> Solution {
-        log(format!("running {:?} algorithm...", BF), false, args.measurement, None);
+    pub fn best_fit(&self, args: Arc<Args>, start_time: Instant) -> Solution {
+        log(LogLevel::Debug, format!("running {:?} algorithm...", BF), None);
 
         let (upper_bound, _lower_bound) = self.global_bounds.get_bounds();
         let machine_count = self.input.get_machine_count();
@@ -44,24 +44,33 @@ impl BFScheduler {
         let mut machine_jobs = MachineJobs::empty(machine_count);
 
         for job_index in 0..self.input.get_job_count() {
-            let mut best_machine = 0;
-            let mut fitting_machine_found = false;
-            for m in 0..machine_count { //man könnte hier speedup erreichen wenn man ab Eingabegröße x eine BH-PQ nutzt...
-                if !fitting_machine_found && machine_jobs.get_machine_workload(m) + jobs[job_index] <= upper_bound {
-                    best_machine = m;
-                    fitting_machine_found = true
-                } else if fitting_machine_found && machine_jobs.get_machine_workload(m) + jobs[job_index] <= upper_bound && machine_jobs.get_machine_workload(m) + jobs[job_index] > machine_jobs.get_machine_workload(best_machine) + jobs[job_index] {
-                    best_machine = m;
+            match find_best_fit_machine(&machine_jobs, jobs[job_index], upper_bound) {
+                None => { //satisfiability check
+                    log(LogLevel::Debug, format!("ERROR: upper bound {} is to low for the {:?}-algorithm with this input", upper_bound, BF), Some(BF));
+                    return Solution::unsatisfiable(BF);
+                }
+                Some(best_machine) => {
+                    machine_jobs.assign_job(jobs[job_index], best_machine, job_index);
                 }
             }
-            if !fitting_machine_found { //satisfiability check
-                log(format!("ERROR: upper bound {} is to low for the {:?}-algorithm with this input", upper_bound, BF), false, args.measurement, Some(BF));
-                return Solution::unsatisfiable(BF);
-            }
-
-            machine_jobs.assign_job(jobs[job_index], best_machine, job_index);
         }
 
-        Solution::new(BF, None, machine_jobs, self.input.get_jobs(), Arc::clone(&self.global_bounds), args, perm, start_time, machine_count)
+        Solution::new(BF, None, machine_jobs, Arc::clone(&self.global_bounds), args, start_time)
+    }
+}
+
+/// returns the most loaded machine that can fit the job without exceeding the capacity (smallest index if there are multiple ones)
+/// None => the job doesn't fit on any machine
+pub fn find_best_fit_machine(machine_jobs: &MachineJobs, job_length: u32, capacity: u32) -> Option<usize> {
+    let mut best_machine = 0;
+    let mut fitting_machine_found = false;
+    for m in 0..machine_jobs.get_machine_count() { //man könnte hier speedup erreichen wenn man ab Eingabegröße x eine BH-PQ nutzt...
+        if !fitting_machine_found && machine_jobs.get_machine_workload(m) + job_length <= capacity {
+            best_machine = m;
+            fitting_machine_found = true
+        } else if fitting_machine_found && machine_jobs.get_machine_workload(m) + job_length <= capacity && machine_jobs.get_machine_workload(m) + job_length > machine_jobs.get_machine_workload(best_machine) + job_length {
+            best_machine = m;
+        }
     }
+    if fitting_machine_found { Some(best_machine) } else { None }
 }
\ No newline at end of file
@@ -8,7 +8,7 @@ use crate::Algorithm::FF;
 use crate::global_bounds::bounds::Bounds;
 use crate::good_solutions::good_solutions::GoodSolutions;
 use crate::input::input::Input;
-use crate::output::log;
+use crate::logging::logger::{log, LogLevel};
 use crate::output::machine_jobs::MachineJobs;
 use crate::output::solution::Solution;
 use crate::schedulers::scheduler::Scheduler;
@@ -19,8 +19,8 @@ pub struct FFScheduler {
 }
 
 impl Scheduler for FFScheduler {
-    fn schedule(&mut self, _good_solutions: GoodSolutions, args: Arc<Args>, perm: Arc<Permutation>, start_time: Instant) -> Solution {
-        self.first_fit(args, perm, start_time)
+    fn schedule(&mut self, _good_solutions: GoodSolutions, args: Arc<Args>, _perm: Arc<Permutation>, start_time: Instant) -> Solution {
+        self.first_fit(args, start_time)
     }
 
     fn get_algorithm(&self) -> Algorithm {
@@ -34,8 +34,8 @@ impl FFScheduler {
     }
 
     /// Assigns the biggest job to the machine with the smallest index until all jobs are assigned
-    pub fn first_fit(&self, args: Arc<Args>, perm: Arc<Permutation>, start_time: Instant) -> Solution {
-        log(format!("running {:?} algorithm...", FF), false, args.measurement, None);
+    pub fn first_fit(&self, args: Arc<Args>, start_time: Instant) -> Solution {
+        log(LogLevel::Debug, format!("running {:?} algorithm...", FF), None);
 
         let (upper_bound, _lower_bound) = self.global_bounds.get_bounds();
         let machine_count = self.input.get_machine_count();
@@ -49,7 +49,7 @@ impl FFScheduler {
             if machine_jobs.get_machine_workload(current_machine) + jobs[job_index] > upper_bound {
                 current_machine += 1;
                 if current_machine == self.input.get_machine_count() { //satisfiability check
-                    log(format!("ERROR: upper bound {} is to low for the {:?}-algorithm with this input", upper_bound, FF), false, args.measurement, Some(FF));
+                    log(LogLevel::Debug, format!("ERROR: upper bound {} is to low for the {:?}-algorithm with this input", upper_bound, FF), Some(FF));
                     return Solution::unsatisfiable(FF);
                 }
             }
@@ -57,6 +57,6 @@ impl FFScheduler {
             machine_jobs.assign_job(jobs[job_index], current_machine, job_index)
         }
 
-        Solution::new(FF, None, machine_jobs, self.input.get_jobs(), Arc::clone(&self.global_bounds), args, perm, start_time, machine_count)
+        Solution::new(FF, None, machine_jobs, Arc::clone(&self.global_bounds), args, start_time)
     }
 }
@@ -8,7 +8,7 @@ use crate::Algorithm::LPT;
 use crate::global_bounds::bounds::Bounds;
 use crate::good_solutions::good_solutions::GoodSolutions;
 use crate::input::input::Input;
-use crate::output::log;
+use crate::logging::logger::{log, LogLevel};
 use crate::output::machine_jobs::MachineJobs;
 use crate::output::solution::Solution;
 use crate::schedulers::scheduler::Scheduler;
@@ -19,8 +19,8 @@ pub struct LPTScheduler {
 }
 
 impl Scheduler for LPTScheduler {
-    fn schedule(&mut self, _good_solutions: GoodSolutions, args: Arc<Args>, perm: Arc<Permutation>, start_time: Instant) -> Solution {
-        self.longest_processing_time(args, perm, start_time)
+    fn schedule(&mut self, _good_solutions: GoodSolutions, args: Arc<Args>, _perm: Arc<Permutation>, start_time: Instant) -> Solution {
+        self.longest_processing_time(args, start_time)
     }
 
     fn get_algorithm(&self) -> Algorithm {
@@ -34,8 +34,8 @@ impl LPTScheduler {
     }
 
     /// Assigns the biggest job to the least loaded machine until all jobs are assigned (= worst fit)
-    fn longest_processing_time(&self, args: Arc<Args>, perm: Arc<Permutation>, start_time: Instant) -> Solution {
-        log(format!("running {:?} algorithm...", LPT), false, args.measurement, None);
+    fn longest_processing_time(&self, args: Arc<Args>, start_time: Instant) -> Solution {
+        log(LogLevel::Debug, format!("running {:?} algorithm...", LPT), None);
 
         let (upper_bound, _lower_bound) = self.global_bounds.get_bounds();
         let machine_count = self.input.get_machine_count();
@@ -48,7 +48,7 @@ impl LPTScheduler {
 
         for job_index in 0..self.input.get_job_count() {
             if machine_jobs.get_machine_workload(current_machine) + jobs[job_index] > upper_bound { //satisfiability check
-                log(format!("ERROR: upper bound {} is to low for the {:?}-algorithm with this input", upper_bound, LPT), false, args.measurement, Some(LPT));
+                log(LogLevel::Debug, format!("ERROR: upper bound {} is to low for the {:?}-algorithm with this input", upper_bound, LPT), Some(LPT));
                 return Solution::unsatisfiable(LPT);
             }
             machine_jobs.assign_job(jobs[job_index], current_machine, job_index);
@@ -68,6 +68,6 @@ impl LPTScheduler {
             }
         }
 
-        Solution::new(LPT, None, machine_jobs, self.input.get_jobs(), Arc::clone(&self.global_bounds), args, perm, start_time, machine_count)
+        Solution::new(LPT, None, machine_jobs, Arc::clone(&self.global_bounds), args, start_time)
     }
 }
@@ -1,3 +1,4 @@
+pub mod beam_search_scheduler;
 pub mod bf_scheduler;
 pub mod ff_scheduler;
 pub mod lpt_scheduler;
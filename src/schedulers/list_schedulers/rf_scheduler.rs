@@ -13,7 +13,7 @@ use crate::global_bounds::bounds::Bounds;
 use crate::good_solutions::good_solutions::GoodSolutions;
 use crate::input::input::Input;
 use crate::input::MyRng;
-use crate::output::log;
+use crate::logging::logger::{log, LogLevel};
 use crate::output::machine_jobs::MachineJobs;
 use crate::output::solution::Solution;
 use crate::schedulers::scheduler::Scheduler;
@@ -26,8 +26,8 @@ pub struct RFScheduler {
 }
 
 impl Scheduler for RFScheduler {
-    fn schedule(&mut self, _good_solutions: GoodSolutions, args: Arc<Args>, perm: Arc<Permutation>, start_time: Instant) -> Solution {
-        self.random_fit(args, perm, start_time, true)
+    fn schedule(&mut self, _good_solutions: GoodSolutions, args: Arc<Args>, _perm: Arc<Permutation>, start_time: Instant) -> Solution {
+        self.random_fit(args, start_time, true)
     }
 
     fn get_algorithm(&self) -> Algorithm {
@@ -37,16 +37,26 @@ impl Scheduler for RFScheduler {
 
 impl RFScheduler {
     pub fn new(input: Arc<Input>, global_bounds: Arc<Bounds>, config: &RFConfig, shared_initial_rng: Arc<Mutex<MyRng>>, higher_level_algo: Option<Algorithm>) -> Self {
-        Self { input: Arc::clone(&input), global_bounds, config: ConcreteRFConfig::new(config, input, shared_initial_rng), higher_level_algo }
+        let rng = shared_initial_rng.lock().unwrap().generate_new_seed().create_rng();
+        Self::with_rng(input, global_bounds, config, rng, higher_level_algo)
     }
 
-    pub fn schedule_without_bounds(&mut self, args: Arc<Args>, perm: Arc<Permutation>, start_time: Instant) -> Solution {
-        self.random_fit(args, perm, start_time, false)
+    /// uses the given rng instead of drawing a seed from the shared initial rng (e.g. resumed from a checkpoint)
+    pub fn with_rng(input: Arc<Input>, global_bounds: Arc<Bounds>, config: &RFConfig, rng: MyRng, higher_level_algo: Option<Algorithm>) -> Self {
+        Self { input: Arc::clone(&input), global_bounds, config: ConcreteRFConfig::new(config, input, rng), higher_level_algo }
+    }
+
+    pub fn get_rng(&self) -> &MyRng {
+        &self.config.rng
+    }
+
+    pub fn schedule_without_bounds(&mut self, args: Arc<Args>, start_time: Instant) -> Solution {
+        self.random_fit(args, start_time, false)
     }
 
     /// Assigns the jobs to random machines
-    pub fn random_fit(&mut self, args: Arc<Args>, perm: Arc<Permutation>, start_time: Instant, use_bounds: bool) -> Solution {
-        log(format!("running {:?} algorithm...", RF), false, args.measurement, self.higher_level_algo);
+    pub fn random_fit(&mut self, args: Arc<Args>, start_time: Instant, use_bounds: bool) -> Solution {
+        log(LogLevel::Debug, format!("running {:?} algorithm...", RF), self.higher_level_algo);
 
         let (upper_bound, _lower_bound) = match use_bounds {
             true => { self.global_bounds.get_bounds() }
@@ -66,10 +76,10 @@ impl RFScheduler {
                     fails += 1;
                     if fails == self.config.fails_until_check {
                         if (0..machine_count).collect::<Vec<_>>().iter().any(|&machine_index| machine_jobs.get_machine_workload(machine_index) + jobs[job_index] <= upper_bound) { //satisfiability check
-                            log(String::from("performed satisfiability check because fails_until_check was reached"), false, args.measurement, Some(RF));
+                            log(LogLevel::Debug, String::from("performed satisfiability check because fails_until_check was reached"), Some(RF));
                             fails = 0;
                         } else {
-                            log(format!("ERROR: upper bound {} is to low for the {:?}-algorithm with this input", upper_bound, RF), false, args.measurement, Some(RF));
+                            log(LogLevel::Debug, format!("ERROR: upper bound {} is to low for the {:?}-algorithm with this input", upper_bound, RF), Some(RF));
                             return Solution::unsatisfiable(RF);
                         }
                     }
@@ -80,7 +90,7 @@ impl RFScheduler {
             machine_jobs.assign_job(jobs[job_index], random_index, job_index)
         }
 
-        Solution::new(RF, Some(self.config.to_string()), machine_jobs, self.input.get_jobs(), Arc::clone(&self.global_bounds), args, perm, start_time, machine_count)
+        Solution::new(RF, Some(self.config.to_string()), machine_jobs, Arc::clone(&self.global_bounds), args, start_time)
     }
 }
 
@@ -91,12 +101,9 @@ pub struct ConcreteRFConfig {
 }
 
 impl ConcreteRFConfig {
-    pub fn new(config: &RFConfig, input: Arc<Input>, shared_initial_rng: Arc<Mutex<MyRng>>) -> Self {
+    pub fn new(config: &RFConfig, input: Arc<Input>, rng: MyRng) -> Self {
         ConcreteRFConfig {
-            rng: {
-                let mut guard = shared_initial_rng.lock().unwrap();
-                guard.generate_new_seed().create_rng()
-            },
+            rng,
             fails_until_check: {
                 match config.fails_until_check {
                     None => { input.get_machine_count() }
@@ -8,7 +8,7 @@ use crate::Algorithm::RR;
 use crate::global_bounds::bounds::Bounds;
 use crate::good_solutions::good_solutions::GoodSolutions;
 use crate::input::input::Input;
-use crate::output::log;
+use crate::logging::logger::{log, LogLevel};
 use crate::output::machine_jobs::MachineJobs;
 use crate::output::solution::Solution;
 use crate::schedulers::scheduler::Scheduler;
@@ -19,8 +19,8 @@ pub struct RRScheduler {
 }
 
 impl Scheduler for RRScheduler {
-    fn schedule(&mut self, _good_solutions: GoodSolutions, args: Arc<Args>, perm: Arc<Permutation>, start_time: Instant) -> Solution {
-        self.round_robin(args, perm, start_time)
+    fn schedule(&mut self, _good_solutions: GoodSolutions, args: Arc<Args>, _perm: Arc<Permutation>, start_time: Instant) -> Solution {
+        self.round_robin(args, start_time)
     }
 
     fn get_algorithm(&self) -> Algorithm {
@@ -34,8 +34,8 @@ impl RRScheduler {
     }
 
     /// Round Robin job assignment
-    pub fn round_robin(&self, args: Arc<Args>, perm: Arc<Permutation>, start_time: Instant) -> Solution {
-        log(format!("running {:?} algorithm...", RR), false, args.measurement, None);
+    pub fn round_robin(&self, args: Arc<Args>, start_time: Instant) -> Solution {
+        log(LogLevel::Debug, format!("running {:?} algorithm...", RR), None);
 
         let (upper_bound, _lower_bound) = self.global_bounds.get_bounds();
         let machine_count = self.input.get_machine_count();
@@ -50,7 +50,7 @@ impl RRScheduler {
             while machine_jobs.get_machine_workload((machine + offset).rem_euclid(self.input.get_machine_count())) + self.input.get_jobs()[job_index] > upper_bound {
                 offset += 1;
                 if offset == self.input.get_machine_count() { //satisfiability check
-                    log(format!("ERROR: upper bound {} is to low for the {:?}-algorithm with this input", upper_bound, RR), false, args.measurement, Some(RR));
+                    log(LogLevel::Debug, format!("ERROR: upper bound {} is to low for the {:?}-algorithm with this input", upper_bound, RR), Some(RR));
                     return Solution::unsatisfiable(RR);
                 }
             }
@@ -59,6 +59,6 @@ impl RRScheduler {
             machine_jobs.assign_job(jobs[job_index], machine, job_index);
         }
 
-        Solution::new(RR, None, machine_jobs, self.input.get_jobs(), Arc::clone(&self.global_bounds), args, perm, start_time, machine_count)
+        Solution::new(RR, None, machine_jobs, Arc::clone(&self.global_bounds), args, start_time)
     }
 }
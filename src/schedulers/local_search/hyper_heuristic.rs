@@ -0,0 +1,256 @@
+use std::str::FromStr;
+use std::string::ParseError;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use permutation::Permutation;
+use rand::distributions::WeightedIndex;
+use rand_distr::{Distribution, Exp};
+
+use crate::{Algorithm, Args};
+use crate::Algorithm::HyperHeuristic;
+use crate::global_bounds::bounds::Bounds;
+use crate::good_solutions::good_solutions::GoodSolutions;
+use crate::input::input::Input;
+use crate::input::MyRng;
+use crate::logging::logger::{log, LogLevel};
+use crate::output::solution::Solution;
+use crate::schedulers::local_search::hyper_heuristic::OperatorSelection::{AdaptivePursuit, Ucb};
+use crate::schedulers::local_search::swapper::{ConcreteSwapConfig, SwapAcceptanceRule, SwapConfig, Swapper, SwapTactic};
+use crate::schedulers::scheduler::Scheduler;
+
+const ADAPTIVE_PURSUIT_ALPHA: f64 = 0.3; //adaption rate of the credit scores
+const ADAPTIVE_PURSUIT_BETA: f64 = 0.3; //learning rate of the selection probabilities
+const ADAPTIVE_PURSUIT_P_MIN: f64 = 0.05; //min. selection probability (gets divided by the number of operators)
+const EPISODES_UNTIL_RESTART: usize = 20; //episodes without improvement until a good solution gets chosen as new current solution
+
+pub struct HyperHeuristicScheduler {
+    global_bounds: Arc<Bounds>,
+    config: HyperHeuristicConfig,
+    rng: MyRng,
+    operators: Vec<Operator>,
+}
+
+impl Scheduler for HyperHeuristicScheduler {
+    fn schedule(&mut self, good_solutions: GoodSolutions, args: Arc<Args>, _perm: Arc<Permutation>, start_time: Instant) -> Solution {
+        self.hyper_heuristic(good_solutions, args, start_time)
+    }
+
+    fn get_algorithm(&self) -> Algorithm {
+        HyperHeuristic
+    }
+}
+
+///Rule how to choose the next operator
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OperatorSelection {
+    ///UCB1 bandit with exploration factor c
+    Ucb(f64),
+    ///adaptive pursuit (probability matching towards the operator with the best credit)
+    AdaptivePursuit,
+}
+
+/// (SwapTactic, SwapAcceptanceRule) pair with its learned credit
+struct Operator {
+    swap_finding_tactic: SwapTactic,
+    swap_acceptance_rule: SwapAcceptanceRule,
+    swapper: Swapper,
+    concrete_swap_config: ConcreteSwapConfig,
+    credit: f64,
+    //average reward (UCB) or exponential recency weighted reward (adaptive pursuit)
+    selection_probability: f64,
+    //only used by adaptive pursuit
+    uses: usize,
+}
+
+impl HyperHeuristicScheduler {
+    pub fn new(input: Arc<Input>, global_bounds: Arc<Bounds>, config: HyperHeuristicConfig, shared_initial_rng: Arc<Mutex<MyRng>>) -> Self {
+        let rng = shared_initial_rng.lock().unwrap().generate_new_seed().create_rng();
+        let operator_count = config.operators.len();
+        let operators = config.operators.iter().map(|&(swap_finding_tactic, swap_acceptance_rule)| {
+            let swap_config = SwapConfig::new(swap_finding_tactic, swap_acceptance_rule);
+            let operator_rng = shared_initial_rng.lock().unwrap().generate_new_seed().create_rng();
+            Operator {
+                swap_finding_tactic,
+                swap_acceptance_rule,
+                concrete_swap_config: ConcreteSwapConfig::new(&swap_config, operator_rng),
+                swapper: Swapper::new(Arc::clone(&input), Arc::clone(&global_bounds), swap_config, Arc::clone(&shared_initial_rng)),
+                credit: 0.0,
+                selection_probability: 1.0 / operator_count as f64,
+                uses: 0,
+            }
+        }).collect();
+        Self { global_bounds, config, rng, operators }
+    }
+
+    /// online selection of (SwapTactic, SwapAcceptanceRule) pairs: each episode the chosen operator runs steps_per_episode swaps on the current solution
+    /// and gets the relative c_max improvement as reward
+    /// all improvements get stored in good_solutions (with the learned operator distribution in their config), the best solution gets returned
+    fn hyper_heuristic(&mut self, good_solutions: GoodSolutions, args: Arc<Args>, start_time: Instant) -> Solution {
+        log(LogLevel::Debug, format!("running {:?} algorithm...", HyperHeuristic), None);
+
+        log(LogLevel::Debug, String::from("waiting for enough good solutions to run HyperHeuristic algorithm..."), Some(HyperHeuristic));
+        if !good_solutions.wait_for_count(1, Some(Duration::from_secs(args.timeout_after).saturating_sub(start_time.elapsed()))) {
+            return Solution::unsatisfiable(HyperHeuristic); //timeout
+        }
+
+        let mut solution = good_solutions.get_best_solution().unwrap();
+        solution.add_algorithm(HyperHeuristic);
+        let mut best_solution = solution.clone();
+        let mut fails = 0;
+
+        while fails < self.config.fails_until_stop && !self.global_bounds.get_cancellation_token().is_cancelled() {
+            let operator_index = self.select_operator();
+            let operator = &mut self.operators[operator_index];
+            let old_c_max = solution.get_data().get_c_max();
+            solution = operator.swapper.local_search(solution, &mut operator.concrete_swap_config, self.config.steps_per_episode);
+            let new_c_max = solution.get_data().get_c_max();
+
+            self.update_credits(operator_index, (old_c_max - new_c_max) as f64 / old_c_max as f64);
+
+            if new_c_max < best_solution.get_data().get_c_max() {
+                best_solution = solution.clone();
+                best_solution.add_config(self.config_string());
+                self.global_bounds.update_upper_bound(new_c_max, &mut best_solution, Arc::clone(&args), start_time, Some(HyperHeuristic));
+                good_solutions.add_solution(best_solution.clone());
+                fails = 0;
+            } else {
+                fails += 1;
+                if fails % EPISODES_UNTIL_RESTART == 0 {
+                    //choose x-th good solution (using exp. distribution):
+                    let x = Exp::new(self.config.lambda).unwrap().sample(self.rng.get_mut()) as usize;
+                    solution = good_solutions.get_x_best_solution(x).unwrap();
+                    solution.add_algorithm(HyperHeuristic);
+                }
+            }
+        }
+
+        log(LogLevel::Debug, self.config_string(), Some(HyperHeuristic));
+        best_solution
+    }
+
+    fn select_operator(&mut self) -> usize {
+        match self.config.operator_selection {
+            Ucb(c) => {
+                if let Some(unused_operator_index) = self.operators.iter().position(|operator| operator.uses == 0) {
+                    return unused_operator_index;
+                }
+                let total_uses = self.operators.iter().map(|operator| operator.uses).sum::<usize>() as f64;
+                let ucb = |operator: &Operator| operator.credit + c * (total_uses.ln() / operator.uses as f64).sqrt();
+                (0..self.operators.len()).max_by(|&a, &b| ucb(&self.operators[a]).total_cmp(&ucb(&self.operators[b]))).unwrap()
+            }
+            AdaptivePursuit => {
+                let distribution = WeightedIndex::new(self.operators.iter().map(|operator| operator.selection_probability)).unwrap();
+                distribution.sample(self.rng.get_mut())
+            }
+        }
+    }
+
+    fn update_credits(&mut self, operator_index: usize, reward: f64) {
+        let operator = &mut self.operators[operator_index];
+        operator.uses += 1;
+        match self.config.operator_selection {
+            Ucb(_) => {
+                operator.credit += (reward - operator.credit) / operator.uses as f64;
+            }
+            AdaptivePursuit => {
+                operator.credit += ADAPTIVE_PURSUIT_ALPHA * (reward - operator.credit);
+
+                let operator_count = self.operators.len() as f64;
+                let p_min = ADAPTIVE_PURSUIT_P_MIN / operator_count;
+                let p_max = 1.0 - (operator_count - 1.0) * p_min;
+                let best_operator_index = (0..self.operators.len()).max_by(|&a, &b| self.operators[a].credit.total_cmp(&self.operators[b].credit)).unwrap();
+                for (i, operator) in self.operators.iter_mut().enumerate() {
+                    let target = if i == best_operator_index { p_max } else { p_min };
+                    operator.selection_probability += ADAPTIVE_PURSUIT_BETA * (target - operator.selection_probability);
+                }
+            }
+        }
+    }
+
+    /// config + learned operator distribution (share of uses, credit)
+    fn config_string(&self) -> String {
+        let total_uses = self.operators.iter().map(|operator| operator.uses).sum::<usize>().max(1) as f64;
+        let distribution: Vec<String> = self.operators.iter().map(|operator| format!("({:?},{:?})={:.3}[credit:{:.6}]", operator.swap_finding_tactic, operator.swap_acceptance_rule, operator.uses as f64 / total_uses, operator.credit)).collect();
+        format!("HYPER_HEURISTIC_CONFIG: OPERATOR_SELECTION:{:?}; STEPS_PER_EPISODE:{}; FAILS_UNTIL_STOP:{}; LAMBDA:{}; RNG:{}; OPERATOR_DISTRIBUTION: {}", self.config.operator_selection, self.config.steps_per_episode, self.config.fails_until_stop, self.config.lambda, self.rng, distribution.join("/"))
+    }
+}
+
+impl OperatorSelection {
+    pub fn from_str(input: &str) -> Result<Self, String> {
+        match input {
+            "ucb" => Ok(Ucb(2f64.sqrt())), //default
+            "adaptive-pursuit" => Ok(AdaptivePursuit),
+            _ => {
+                match input.strip_prefix("ucb-").map(|c| c.parse::<f64>()) {
+                    Some(Ok(c)) => Ok(Ucb(c)),
+                    _ => Err(format!("invalid variant: {input}"))
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct HyperHeuristicConfig {
+    operator_selection: OperatorSelection,
+    operators: Vec<(SwapTactic, SwapAcceptanceRule)>,
+    steps_per_episode: usize,
+    fails_until_stop: usize,
+    //episodes without improvement until the search stops
+    lambda: f64,
+}
+
+impl FromStr for HyperHeuristicConfig {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split(",").collect();
+        Ok(HyperHeuristicConfig {
+            operator_selection: {
+                if !parts[0].is_empty() {
+                    OperatorSelection::from_str(parts[0]).unwrap()
+                } else {
+                    //default:
+                    Ucb(2f64.sqrt())
+                }
+            },
+            operators: {
+                let operators_str = if parts.len() > 1 && !parts[1].is_empty() {
+                    parts[1]
+                } else {
+                    //default:
+                    "two-job-best-swap:improvement/two-job-best-swap:improvement-or-rs-by-5%-chance/two-job-random-swap:improvement/two-job-random-swap:decline-by-5%-chance/two-job-random-swap:all/pair-reoptimization:improvement"
+                };
+                operators_str.split("/").map(|operator| {
+                    let (swap_finding_tactic, swap_acceptance_rule) = operator.split_once(':').unwrap();
+                    (SwapTactic::from_str(swap_finding_tactic).unwrap(), SwapAcceptanceRule::from_str(swap_acceptance_rule).unwrap())
+                }).collect()
+            },
+            steps_per_episode: {
+                if parts.len() > 2 && !parts[2].is_empty() {
+                    parts[2].parse::<usize>().unwrap()
+                } else {
+                    //default:
+                    100
+                }
+            },
+            fails_until_stop: {
+                if parts.len() > 3 && !parts[3].is_empty() {
+                    parts[3].parse::<usize>().unwrap()
+                } else {
+                    //default:
+                    500
+                }
+            },
+            lambda: {
+                if parts.len() > 4 && !parts[4].is_empty() { //0.1 - inf
+                    parts[4].parse::<f64>().unwrap()
+                } else {
+                    //default:
+                    0.5
+                }
+            },
+        })
+    }
+}
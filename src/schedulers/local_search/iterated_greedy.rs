@@ -0,0 +1,228 @@
+use std::str::FromStr;
+use std::string::ParseError;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use permutation::Permutation;
+use rand::Rng;
+use rand::seq::index::sample;
+
+use crate::{Algorithm, Args};
+use crate::Algorithm::IG;
+use crate::global_bounds::bounds::Bounds;
+use crate::good_solutions::good_solutions::GoodSolutions;
+use crate::input::input::Input;
+use crate::input::MyRng;
+use crate::logging::logger::{log, LogLevel};
+use crate::output::machine_jobs::MachineJobs;
+use crate::output::solution::Solution;
+use crate::schedulers::list_schedulers::bf_scheduler::find_best_fit_machine;
+use crate::schedulers::local_search::iterated_greedy::Destruction::{Critical, Random};
+use crate::schedulers::local_search::iterated_greedy::Reconstruction::{BestFit, LeastLoaded};
+use crate::schedulers::scheduler::Scheduler;
+
+pub struct IGScheduler {
+    input: Arc<Input>,
+    global_bounds: Arc<Bounds>,
+    config: IGConfig,
+    rng: MyRng,
+}
+
+impl Scheduler for IGScheduler {
+    fn schedule(&mut self, good_solutions: GoodSolutions, args: Arc<Args>, _perm: Arc<Permutation>, start_time: Instant) -> Solution {
+        self.iterated_greedy(good_solutions, args, start_time)
+    }
+
+    fn get_algorithm(&self) -> Algorithm {
+        IG
+    }
+}
+
+///Which jobs get removed from the schedule
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Destruction {
+    ///d random jobs
+    Random,
+    ///d random jobs of the heaviest machine (+ random other jobs if it has less than d jobs)
+    Critical,
+}
+
+///How the removed jobs get reinserted (descending by length)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Reconstruction {
+    ///on the least loaded machine
+    LeastLoaded,
+    ///on the most loaded machine that stays below the c_max before the destruction (least loaded machine if none fits)
+    BestFit,
+}
+
+impl IGScheduler {
+    pub fn new(input: Arc<Input>, global_bounds: Arc<Bounds>, config: IGConfig, shared_initial_rng: Arc<Mutex<MyRng>>) -> Self {
+        let rng = shared_initial_rng.lock().unwrap().generate_new_seed().create_rng();
+        Self { input, global_bounds, config, rng }
+    }
+
+    /// repeatedly removes d jobs and reinserts them greedily, the new schedule gets accepted by a Metropolis criterion
+    /// (temperature = temperature_factor * sum of all job lengths / (n * m * 10))
+    /// all improvements get stored in good_solutions, the best solution gets returned
+    fn iterated_greedy(&mut self, good_solutions: GoodSolutions, args: Arc<Args>, start_time: Instant) -> Solution {
+        log(LogLevel::Debug, format!("running {:?} algorithm...", IG), None);
+
+        log(LogLevel::Debug, String::from("waiting for enough good solutions to run IG algorithm..."), Some(IG));
+        if !good_solutions.wait_for_count(1, Some(Duration::from_secs(args.timeout_after).saturating_sub(start_time.elapsed()))) {
+            return Solution::unsatisfiable(IG); //timeout
+        }
+
+        let input = Arc::clone(&self.input);
+        let jobs = input.get_jobs();
+        let machine_count = input.get_machine_count();
+        let temperature = self.config.temperature_factor * jobs.iter().map(|&job_length| job_length as f64).sum::<f64>() / (jobs.len() * machine_count * 10) as f64;
+        let config_str = format!("IG_CONFIG: DESTRUCTION_SIZE:{}; DESTRUCTION:{:?}; RECONSTRUCTION:{:?}; TEMPERATURE_FACTOR:{}; FAILS_UNTIL_STOP:{}; RNG:{}", self.config.destruction_size, self.config.destruction, self.config.reconstruction, self.config.temperature_factor, self.config.fails_until_stop, self.rng);
+
+        let mut best_solution = good_solutions.get_best_solution().unwrap();
+        let mut machine_jobs = best_solution.get_data().get_machine_jobs().clone();
+        let mut fails = 0;
+
+        while fails < self.config.fails_until_stop && !self.global_bounds.get_cancellation_token().is_cancelled() {
+            let mut new_machine_jobs = machine_jobs.clone();
+            let removed_jobs = self.destroy(&mut new_machine_jobs);
+            self.reconstruct(&mut new_machine_jobs, removed_jobs, machine_jobs.get_c_max());
+
+            let (c_max, new_c_max) = (machine_jobs.get_c_max(), new_machine_jobs.get_c_max());
+            if new_c_max < best_solution.get_data().get_c_max() {
+                best_solution = Solution::new(IG, Some(config_str.clone()), new_machine_jobs.clone(), Arc::clone(&self.global_bounds), Arc::clone(&args), start_time);
+                good_solutions.add_solution(best_solution.clone());
+                fails = 0;
+            } else {
+                fails += 1;
+            }
+
+            //Metropolis acceptance:
+            if new_c_max <= c_max || self.rng.get_mut().gen_bool((-((new_c_max - c_max) as f64) / temperature).exp()) {
+                machine_jobs = new_machine_jobs;
+            }
+        }
+
+        best_solution
+    }
+
+    /// removes destruction_size jobs and returns their general job indices
+    fn destroy(&mut self, machine_jobs: &mut MachineJobs) -> Vec<usize> {
+        let input = Arc::clone(&self.input);
+        let jobs = input.get_jobs();
+        let destruction_size = self.config.destruction_size.min(jobs.len());
+        let mut removed_jobs = Vec::with_capacity(destruction_size);
+
+        if self.config.destruction == Critical {
+            let heaviest_machine_index = machine_jobs.get_heaviest_machine_index();
+            let heaviest_machine_job_count = machine_jobs.get_machine_jobs(heaviest_machine_index).len();
+            let mut positions = sample(self.rng.get_mut(), heaviest_machine_job_count, destruction_size.min(heaviest_machine_job_count)).into_vec();
+            positions.sort_by(|a, b| b.cmp(a)); //remove from the back => the other positions stay valid
+            for position in positions {
+                removed_jobs.push(machine_jobs.remove_job(heaviest_machine_index, position, jobs));
+            }
+        }
+
+        while removed_jobs.len() < destruction_size {
+            let machine_index = self.rng.get_mut().gen_range(0..machine_jobs.get_machine_count());
+            let machine_job_count = machine_jobs.get_machine_jobs(machine_index).len();
+            if machine_job_count > 0 {
+                let position = self.rng.get_mut().gen_range(0..machine_job_count);
+                removed_jobs.push(machine_jobs.remove_job(machine_index, position, jobs));
+            }
+        }
+        removed_jobs
+    }
+
+    /// reinserts the jobs descending by length (job indices are sorted by descending length)
+    fn reconstruct(&self, machine_jobs: &mut MachineJobs, mut removed_jobs: Vec<usize>, capacity: u32) {
+        let jobs = self.input.get_jobs();
+        removed_jobs.sort();
+        for job_index in removed_jobs {
+            let machine_index = match self.config.reconstruction {
+                LeastLoaded => { machine_jobs.get_lightest_machine_index() }
+                BestFit => { find_best_fit_machine(machine_jobs, jobs[job_index], capacity).unwrap_or(machine_jobs.get_lightest_machine_index()) }
+            };
+            machine_jobs.assign_job(jobs[job_index], machine_index, job_index);
+        }
+    }
+}
+
+impl Destruction {
+    pub fn from_str(input: &str) -> Result<Self, String> {
+        match input {
+            "random" => Ok(Random),
+            "critical" => Ok(Critical),
+            _ => Err(format!("invalid variant: {input}"))
+        }
+    }
+}
+
+impl Reconstruction {
+    pub fn from_str(input: &str) -> Result<Self, String> {
+        match input {
+            "lpt" => Ok(LeastLoaded),
+            "bf" => Ok(BestFit),
+            _ => Err(format!("invalid variant: {input}"))
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct IGConfig {
+    destruction_size: usize,
+    destruction: Destruction,
+    reconstruction: Reconstruction,
+    temperature_factor: f64,
+    fails_until_stop: usize, //iterations without improvement until the search stops
+}
+
+impl FromStr for IGConfig {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split(",").collect();
+        Ok(IGConfig {
+            destruction_size: {
+                if !parts[0].is_empty() {
+                    parts[0].parse::<usize>().unwrap()
+                } else {
+                    //default:
+                    4
+                }
+            },
+            destruction: {
+                if parts.len() > 1 && !parts[1].is_empty() {
+                    Destruction::from_str(parts[1]).unwrap()
+                } else {
+                    //default:
+                    Critical
+                }
+            },
+            reconstruction: {
+                if parts.len() > 2 && !parts[2].is_empty() {
+                    Reconstruction::from_str(parts[2]).unwrap()
+                } else {
+                    //default:
+                    LeastLoaded
+                }
+            },
+            temperature_factor: {
+                if parts.len() > 3 && !parts[3].is_empty() {
+                    parts[3].parse::<f64>().unwrap()
+                } else {
+                    //default:
+                    0.5
+                }
+            },
+            fails_until_stop: {
+                if parts.len() > 4 && !parts[4].is_empty() {
+                    parts[4].parse::<usize>().unwrap()
+                } else {
+                    //default:
+                    10000
+                }
+            },
+        })
+    }
+}
@@ -0,0 +1,264 @@
+use std::cmp::{max, min};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use permutation::Permutation;
+use rand::seq::index::sample;
+
+use crate::{Algorithm, Args};
+use crate::Algorithm::LNS;
+use crate::global_bounds::bounds::Bounds;
+use crate::good_solutions::good_solutions::GoodSolutions;
+use crate::input::input::Input;
+use crate::input::MyRng;
+use crate::logging::logger::{log, LogLevel};
+use crate::output::solution::Solution;
+use crate::schedulers::scheduler::Scheduler;
+
+pub struct LNSScheduler {
+    input: Arc<Input>,
+    global_bounds: Arc<Bounds>,
+    config: LNSConfig,
+    rng: MyRng,
+}
+
+impl Scheduler for LNSScheduler {
+    fn schedule(&mut self, good_solutions: GoodSolutions, args: Arc<Args>, _perm: Arc<Permutation>, start_time: Instant) -> Solution {
+        self.large_neighborhood_search(good_solutions, args, start_time)
+    }
+
+    fn get_algorithm(&self) -> Algorithm {
+        LNS
+    }
+}
+
+/// state of the branch and bound used to repair the destroyed machines
+struct Repair<'a> {
+    job_lengths: &'a [u32],
+    //sorted descending
+    remaining_workload: Vec<u32>,
+    //remaining_workload[i] = sum of job_lengths[i..]
+    loads: Vec<u32>,
+    assignment: Vec<usize>,
+    best_assignment: Option<Vec<usize>>,
+    best_max_load: u32,
+    lower_bound: u32,
+    nodes: usize,
+    node_limit: usize,
+}
+
+impl LNSScheduler {
+    pub fn new(input: Arc<Input>, global_bounds: Arc<Bounds>, config: LNSConfig, shared_initial_rng: Arc<Mutex<MyRng>>) -> Self {
+        let rng = shared_initial_rng.lock().unwrap().generate_new_seed().create_rng();
+        Self { input, global_bounds, config, rng }
+    }
+
+    /// repeatedly removes all jobs of k machines (the heaviest one + random ones) and reinserts them optimally (branch and bound)
+    /// results that are no worse get accepted, k grows when the search stagnates
+    /// all improvements get stored in good_solutions, the best solution gets returned
+    fn large_neighborhood_search(&mut self, good_solutions: GoodSolutions, args: Arc<Args>, start_time: Instant) -> Solution {
+        log(LogLevel::Debug, format!("running {:?} algorithm...", LNS), None);
+
+        log(LogLevel::Debug, String::from("waiting for enough good solutions to run LNS algorithm..."), Some(LNS));
+        if !good_solutions.wait_for_count(1, Some(Duration::from_secs(args.timeout_after).saturating_sub(start_time.elapsed()))) {
+            return Solution::unsatisfiable(LNS); //timeout
+        }
+
+        let machine_count = self.input.get_machine_count();
+        let k_max = min(self.config.k_max, machine_count);
+        let k_min = min(self.config.k_min, k_max);
+
+        let mut solution = good_solutions.get_best_solution().unwrap();
+        solution.add_algorithm(LNS);
+        solution.add_config(format!("LNS_CONFIG: K_MIN:{}; K_MAX:{}; STAGNATION_LIMIT:{}; NODE_LIMIT:{}; FAILS_UNTIL_STOP:{}; RNG:{}", self.config.k_min, self.config.k_max, self.config.stagnation_limit, self.config.node_limit, self.config.fails_until_stop, self.rng));
+        let mut best_solution = solution.clone();
+
+        if machine_count < 2 {
+            return best_solution;
+        }
+
+        let new_best_solutions = good_solutions.subscribe_to_new_best_solutions();
+        let (mut k, mut stagnation, mut fails) = (k_min, 0, 0);
+        while fails < self.config.fails_until_stop && !self.global_bounds.get_cancellation_token().is_cancelled() {
+            //continue with better solutions found by other algorithms:
+            while let Ok(new_best_solution) = new_best_solutions.try_recv() {
+                if new_best_solution.get_data().get_c_max() < solution.get_data().get_c_max() {
+                    solution = new_best_solution;
+                }
+            }
+
+            let machines = self.select_machines(&solution, k);
+            self.destroy_and_repair(&mut solution, &machines);
+
+            if solution.get_data().get_c_max() < best_solution.get_data().get_c_max() {
+                best_solution = solution.clone();
+                self.global_bounds.update_upper_bound(solution.get_data().get_c_max(), &mut best_solution, Arc::clone(&args), start_time, Some(LNS));
+                good_solutions.add_solution(best_solution.clone());
+                (k, stagnation, fails) = (k_min, 0, 0);
+            } else {
+                stagnation += 1;
+                fails += 1;
+                if stagnation == self.config.stagnation_limit {
+                    k = min(k + 1, k_max);
+                    stagnation = 0;
+                }
+            }
+        }
+
+        best_solution
+    }
+
+    /// the heaviest machine + k-1 random other machines
+    fn select_machines(&mut self, solution: &Solution, k: usize) -> Vec<usize> {
+        let heaviest_machine_index = solution.get_data().get_machine_jobs().get_heaviest_machine_index();
+        let mut machines = vec![heaviest_machine_index];
+        for m in sample(self.rng.get_mut(), self.input.get_machine_count() - 1, k - 1) {
+            machines.push(if m < heaviest_machine_index { m } else { m + 1 });
+        }
+        machines
+    }
+
+    /// removes all jobs from the given machines and reinserts them optimally if that is no worse (bounded by the current max workload of these machines)
+    fn destroy_and_repair(&self, solution: &mut Solution, machines: &[usize]) {
+        let jobs = self.input.get_jobs();
+        let machine_jobs = solution.get_data().get_machine_jobs();
+
+        let old_max_load = machines.iter().map(|&m| machine_jobs.get_machine_workload(m)).max().unwrap();
+        let mut removed_jobs: Vec<usize> = machines.iter().flat_map(|&m| machine_jobs.get_machine_jobs(m).iter().copied()).collect();
+        removed_jobs.sort(); //=> descending job lengths
+        let job_lengths: Vec<u32> = removed_jobs.iter().map(|&job_index| jobs[job_index]).collect();
+
+        let mut remaining_workload = vec![0; job_lengths.len() + 1];
+        for i in (0..job_lengths.len()).rev() {
+            remaining_workload[i] = remaining_workload[i + 1] + job_lengths[i];
+        }
+        let lower_bound = max(remaining_workload[0].div_ceil(machines.len() as u32), *job_lengths.first().unwrap_or(&0));
+
+        let mut repair = Repair {
+            job_lengths: &job_lengths,
+            remaining_workload,
+            loads: vec![0; machines.len()],
+            assignment: vec![0; job_lengths.len()],
+            best_assignment: None,
+            best_max_load: old_max_load + 1, //=> solutions that are no worse are accepted
+            lower_bound,
+            nodes: 0,
+            node_limit: self.config.node_limit,
+        };
+        Self::branch_and_bound(&mut repair, 0, 0);
+
+        if let Some(assignment) = repair.best_assignment {
+            let mut new_machine_jobs = vec![vec![]; machines.len()];
+            for (i, &job_index) in removed_jobs.iter().enumerate() {
+                new_machine_jobs[assignment[i]].push(job_index);
+            }
+            for (i, job_indices) in new_machine_jobs.into_iter().enumerate() {
+                solution.set_machine_jobs(machines[i], job_indices, jobs);
+            }
+        }
+    }
+
+    /// assigns job i to all machines (skipping machines with equal loads) and prunes with the best max load found so far
+    fn branch_and_bound(repair: &mut Repair, i: usize, current_max_load: u32) {
+        repair.nodes += 1;
+        if i == repair.job_lengths.len() {
+            repair.best_max_load = current_max_load;
+            repair.best_assignment = Some(repair.assignment.clone());
+            return;
+        }
+
+        let machine_count = repair.loads.len() as u32;
+        let assigned_workload: u32 = repair.loads.iter().sum();
+        if max(current_max_load, (assigned_workload + repair.remaining_workload[i]).div_ceil(machine_count)) >= repair.best_max_load {
+            return;
+        }
+
+        for m in 0..repair.loads.len() {
+            if repair.loads[..m].contains(&repair.loads[m]) {
+                continue; //symmetric to an already tried machine
+            }
+            let new_load = repair.loads[m] + repair.job_lengths[i];
+            if new_load >= repair.best_max_load {
+                continue;
+            }
+
+            repair.loads[m] = new_load;
+            repair.assignment[i] = m;
+            Self::branch_and_bound(repair, i + 1, max(current_max_load, new_load));
+            repair.loads[m] -= repair.job_lengths[i];
+
+            if repair.best_max_load == repair.lower_bound || repair.nodes >= repair.node_limit {
+                return; //optimal or out of budget
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LNSConfig {
+    k_min: usize,
+    k_max: usize,
+    stagnation_limit: usize,
+    //iterations without improvement until k gets increased
+    node_limit: usize,
+    //max number of branch and bound nodes per repair
+    fails_until_stop: usize, //iterations without improvement until the search stops
+}
+
+impl FromStr for LNSConfig {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split(",").collect();
+        let config = LNSConfig {
+            k_min: {
+                if !parts[0].is_empty() {
+                    parts[0].parse::<usize>().unwrap()
+                } else {
+                    //default:
+                    2
+                }
+            },
+            k_max: {
+                if parts.len() > 1 && !parts[1].is_empty() {
+                    parts[1].parse::<usize>().unwrap()
+                } else {
+                    //default:
+                    5
+                }
+            },
+            stagnation_limit: {
+                if parts.len() > 2 && !parts[2].is_empty() {
+                    parts[2].parse::<usize>().unwrap()
+                } else {
+                    //default:
+                    20
+                }
+            },
+            node_limit: {
+                if parts.len() > 3 && !parts[3].is_empty() {
+                    parts[3].parse::<usize>().unwrap()
+                } else {
+                    //default:
+                    100000
+                }
+            },
+            fails_until_stop: {
+                if parts.len() > 4 && !parts[4].is_empty() {
+                    parts[4].parse::<usize>().unwrap()
+                } else {
+                    //default:
+                    1000
+                }
+            },
+        };
+        if config.k_min < 1 {
+            return Err(format!("k_min has to be at least 1: {s}"));
+        }
+        if config.k_min > config.k_max {
+            return Err(format!("k_min has to be <= k_max: {s}"));
+        }
+        Ok(config)
+    }
+}
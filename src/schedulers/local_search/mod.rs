@@ -1,3 +1,9 @@
+pub mod hyper_heuristic;
+pub mod iterated_greedy;
+pub mod lns;
+pub mod objective;
+pub mod subset_sum;
 pub mod swapper;
+pub mod vns;
 
 // Schedulers using algorithms from the Local Search family to find better solutions
@@ -0,0 +1,115 @@
+use std::cmp::Ordering;
+
+use crate::output::machine_jobs::MachineJobs;
+use crate::schedulers::local_search::objective::ObjectiveKind::{CMax, CriticalMachines, L2Norm, Lexicographic};
+
+///Which values are compared (lexicographically) to rate a solution during the local search
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ObjectiveKind {
+    ///only c_max
+    CMax,
+    ///c_max, then the number of machines with workload c_max
+    CriticalMachines,
+    ///c_max, then the sum of the squared machine workloads
+    L2Norm,
+    ///c_max, then the number of machines with workload c_max, then the sum of the squared machine workloads
+    Lexicographic,
+}
+
+/// rating of a solution (smaller is better), only the values used by the kind get compared
+#[derive(Clone, Copy, Debug)]
+pub struct Objective {
+    kind: ObjectiveKind,
+    c_max: u32,
+    critical_machines: usize,
+    //number of machines with workload c_max
+    squared_workload_sum: u64,
+}
+
+impl Objective {
+    /// O(log m) for c_max and the critical machines, O(m) if the squared workload sum is used
+    /// values that the kind doesn't compare are not computed (0)
+    pub fn new(kind: ObjectiveKind, machine_jobs: &MachineJobs) -> Self {
+        let c_max = machine_jobs.get_c_max();
+        Self {
+            kind,
+            c_max,
+            critical_machines: if kind.uses_critical_machines() { machine_jobs.count_machines_with_workload_without(c_max, &[]) } else { 0 },
+            squared_workload_sum: if kind.uses_squared_workload_sum() { machine_jobs.get_squared_workload_sum() } else { 0 },
+        }
+    }
+
+    pub fn get_kind(&self) -> ObjectiveKind {
+        self.kind
+    }
+
+    /// objective of the same kind with other values
+    pub fn with_values(&self, c_max: u32, critical_machines: usize, squared_workload_sum: u64) -> Self {
+        Self { kind: self.kind, c_max, critical_machines, squared_workload_sum }
+    }
+
+    pub fn get_c_max(&self) -> u32 {
+        self.c_max
+    }
+
+    pub fn get_squared_workload_sum(&self) -> u64 {
+        self.squared_workload_sum
+    }
+
+    fn key(&self) -> (u32, usize, u64) {
+        match self.kind {
+            CMax => (self.c_max, 0, 0),
+            CriticalMachines => (self.c_max, self.critical_machines, 0),
+            L2Norm => (self.c_max, 0, self.squared_workload_sum),
+            Lexicographic => (self.c_max, self.critical_machines, self.squared_workload_sum),
+        }
+    }
+}
+
+impl PartialEq for Objective {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Objective {}
+
+impl PartialOrd for Objective {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Objective {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl ObjectiveKind {
+    /// c_max alone can't rate the moves on a plateau (several machines with workload c_max) => the number of critical machines breaks the ties
+    pub fn with_plateau_tie_breaker(self) -> Self {
+        match self {
+            CMax => { CriticalMachines }
+            kind => { kind }
+        }
+    }
+
+    pub fn uses_critical_machines(&self) -> bool {
+        matches!(self, CriticalMachines | Lexicographic)
+    }
+
+    pub fn uses_squared_workload_sum(&self) -> bool {
+        matches!(self, L2Norm | Lexicographic)
+    }
+
+    pub fn from_str(input: &str) -> Result<Self, String> {
+        match input {
+            "c-max" => Ok(CMax),
+            "critical-machines" => Ok(CriticalMachines),
+            "l2-norm" => Ok(L2Norm),
+            "lexicographic" => Ok(Lexicographic),
+            _ => Err(format!("invalid variant: {input}"))
+        }
+    }
+}
@@ -0,0 +1,126 @@
+/// Bitset of reachable subset sums 0..=capacity
+#[derive(Clone, Debug)]
+struct SumBitset(Vec<u64>);
+
+impl SumBitset {
+    fn zero(capacity: usize) -> Self {
+        let mut bitset = Self(vec![0; capacity / 64 + 1]);
+        bitset.0[0] = 1; //the empty subset
+        bitset
+    }
+
+    fn contains(&self, sum: usize) -> bool {
+        self.0[sum / 64] >> (sum % 64) & 1 == 1
+    }
+
+    /// self | (self << shift) (sums above the capacity get cut off)
+    fn or_shifted(&self, shift: usize, capacity: usize) -> Self {
+        let mut result = self.clone();
+        let (word_shift, bit_shift) = (shift / 64, shift % 64);
+        for w in (word_shift..self.0.len()).rev() {
+            let mut shifted = self.0[w - word_shift] << bit_shift;
+            if bit_shift > 0 && w > word_shift {
+                shifted |= self.0[w - word_shift - 1] >> (64 - bit_shift);
+            }
+            result.0[w] |= shifted;
+        }
+        let last_bits = capacity % 64 + 1;
+        if last_bits < 64 {
+            *result.0.last_mut().unwrap() &= (1u64 << last_bits) - 1;
+        }
+        result
+    }
+}
+
+/// greatest common divisor of the lengths (0 if there are none) => all subset sums are multiples of it
+pub fn lengths_gcd(lengths: &[u32]) -> u32 {
+    lengths.iter().fold(0, |a, &b| {
+        let (mut a, mut b) = (a, b);
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        a
+    })
+}
+
+/// returns the indices of a subset of lengths with the largest sum <= capacity (bitset DP in O(n*capacity/64))
+pub fn max_subset_sum(lengths: &[u32], capacity: u32) -> Vec<usize> {
+    let capacity = capacity as usize;
+
+    //reachable[i] = reachable sums using the first i lengths
+    let mut reachable = Vec::with_capacity(lengths.len() + 1);
+    reachable.push(SumBitset::zero(capacity));
+    for (i, &length) in lengths.iter().enumerate() {
+        let next = if length as usize > capacity { reachable[i].clone() } else { reachable[i].or_shifted(length as usize, capacity) };
+        reachable.push(next);
+    }
+
+    let mut sum = (0..=capacity).rev().find(|&s| reachable[lengths.len()].contains(s)).unwrap();
+
+    //reconstruct the subset:
+    let mut subset = vec![];
+    for i in (0..lengths.len()).rev() {
+        if !reachable[i].contains(sum) {
+            subset.push(i);
+            sum -= lengths[i] as usize;
+        }
+    }
+    debug_assert_eq!(sum, 0);
+    subset
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn subset_sum(lengths: &[u32], subset: &[usize]) -> u32 {
+        subset.iter().map(|&i| lengths[i]).sum()
+    }
+
+    #[test]
+    fn finds_the_largest_sum_below_the_capacity() {
+        let lengths = [8, 6, 5, 3];
+        assert_eq!(subset_sum(&lengths, &max_subset_sum(&lengths, 12)), 11);
+        assert_eq!(subset_sum(&lengths, &max_subset_sum(&lengths, 13)), 13);
+        assert_eq!(subset_sum(&lengths, &max_subset_sum(&lengths, 2)), 0);
+        assert_eq!(subset_sum(&lengths, &max_subset_sum(&lengths, 100)), 22);
+    }
+
+    #[test]
+    fn returns_distinct_indices() {
+        let lengths = [4, 4, 4, 4, 4];
+        let mut subset = max_subset_sum(&lengths, 10);
+        assert_eq!(subset_sum(&lengths, &subset), 8);
+        subset.sort();
+        subset.dedup();
+        assert_eq!(subset.len(), 2);
+    }
+
+    #[test]
+    fn works_across_word_boundaries() {
+        let lengths = [63, 64, 65, 130, 1];
+        assert_eq!(subset_sum(&lengths, &max_subset_sum(&lengths, 129)), 129);
+        assert_eq!(subset_sum(&lengths, &max_subset_sum(&lengths, 194)), 194);
+        assert_eq!(subset_sum(&lengths, &max_subset_sum(&lengths, 200)), 196);
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let lengths = [17, 23, 5, 99, 42, 8, 61, 3];
+        for capacity in 0..=260 {
+            let brute_force = (0..1u32 << lengths.len())
+                .map(|mask| (0..lengths.len()).filter(|i| mask >> i & 1 == 1).map(|i| lengths[i]).sum::<u32>())
+                .filter(|&sum| sum <= capacity)
+                .max()
+                .unwrap();
+            assert_eq!(subset_sum(&lengths, &max_subset_sum(&lengths, capacity)), brute_force, "capacity {capacity}");
+        }
+    }
+
+    #[test]
+    fn gcd_of_the_lengths() {
+        assert_eq!(lengths_gcd(&[12, 18, 30]), 6);
+        assert_eq!(lengths_gcd(&[7, 5]), 1);
+        assert_eq!(lengths_gcd(&[]), 0);
+    }
+}
@@ -1,10 +1,9 @@
-use std::cmp::{max, PartialEq};
+use std::cmp::{max, min, PartialEq};
 use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::str::FromStr;
-use std::string::ParseError;
 use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use atoi::atoi;
 use permutation::Permutation;
@@ -12,32 +11,44 @@ use rand::Rng;
 use rand_distr::Distribution;
 use rand_distr::Exp;
 use rayon::current_num_threads;
+use rayon::prelude::*;
 use regex::Regex;
 
 use crate::{Algorithm, Args};
 use crate::Algorithm::Swap;
+use crate::checkpoint::checkpoint_file::{Checkpoint, SwapWorkerState};
 use crate::global_bounds::bounds::Bounds;
 use crate::good_solutions::good_solutions::GoodSolutions;
+use crate::good_solutions::persistence::good_solutions_to_string;
 use crate::input::input::Input;
 use crate::input::MyRng;
-use crate::output::log;
+use crate::logging::logger::{log, LogLevel};
 use crate::output::machine_jobs::MachineJobs;
 use crate::output::solution::Solution;
 use crate::schedulers::list_schedulers::rf_scheduler::{RFConfig, RFScheduler};
-use crate::schedulers::local_search::swapper::SwapAcceptanceRule::{All, DeclineByChance, Improvement, ImprovementOrRsByChance};
-use crate::schedulers::local_search::swapper::SwapTactic::{TwoJobBestSwap, TwoJobRandomSwap};
+use crate::schedulers::local_search::objective::{Objective, ObjectiveKind};
+use crate::schedulers::local_search::objective::ObjectiveKind::CMax;
+use crate::schedulers::local_search::subset_sum::{lengths_gcd, max_subset_sum};
+use crate::schedulers::local_search::swapper::IslandTopology::{FullyConnected, Ring};
+use crate::schedulers::local_search::swapper::MigrationPolicy::{BestMigrant, DiversityPreserving, RandomMigrant};
+use crate::schedulers::local_search::swapper::SwapAcceptanceRule::{All, DeclineByChance, GreatDeluge, Improvement, ImprovementOrRsByChance, LateAcceptance, ThresholdAccepting};
+use crate::schedulers::local_search::swapper::SwapTactic::{AllPairsBestSwap, AllPairsFirstSwap, PairReoptimization, TwoJobBestSwap, TwoJobRandomSwap};
 use crate::schedulers::scheduler::Scheduler;
 
+const PARALLEL_PARTNER_EVALUATION_THRESHOLD: usize = 64; //min. number of partner machines to evaluate them in parallel (all pairs swap)
+
 pub struct Swapper {
     input: Arc<Input>,
     global_bounds: Arc<Bounds>,
     config: SwapConfig,
     shared_initial_rng: Arc<Mutex<MyRng>>,
+    checkpoint: Option<(Arc<Checkpoint>, usize)>,
+    //(checkpoint the worker states get written to/resumed from, config id)
 }
 
 impl Scheduler for Swapper {
-    fn schedule(&mut self, good_solutions: GoodSolutions, args: Arc<Args>, perm: Arc<Permutation>, start_time: Instant) -> Solution {
-        self.swap(good_solutions, args, perm, start_time)
+    fn schedule(&mut self, good_solutions: GoodSolutions, args: Arc<Args>, _perm: Arc<Permutation>, start_time: Instant) -> Solution {
+        self.swap(good_solutions, args, start_time)
     }
 
     fn get_algorithm(&self) -> Algorithm {
@@ -50,6 +61,12 @@ impl Scheduler for Swapper {
 pub enum SwapTactic {
     TwoJobBestSwap,
     TwoJobRandomSwap(usize), //fails_until_stop
+    ///optimally redistributes the jobs of the heaviest machine and a partner machine (subset-sum DP)
+    PairReoptimization,
+    ///best improving 2 job swap between any machine with workload c_max and any partner machine
+    AllPairsBestSwap,
+    ///first improving 2 job swap between any machine with workload c_max and any partner machine (partners from the lightest to the heaviest)
+    AllPairsFirstSwap,
 }
 
 ///Rule when to accept a swap
@@ -63,6 +80,32 @@ pub enum SwapAcceptanceRule {
     DeclineByChance(u8),
     ///accept all swaps independent of c_max
     All,
+    ///late acceptance hill climbing: accept if c_max is no worse than the current one or the one L iterations ago (history length L)
+    LateAcceptance(usize),
+    ///accept if c_max gets worse by at most the threshold (initial_threshold, threshold decrease in percent after each accepted swap)
+    ThresholdAccepting(u32, u8),
+    ///accept if c_max is below the water level, which starts at the initial c_max and drops by rain_speed/1000 after each accepted swap
+    GreatDeluge(u32),
+}
+
+///Topology of the island model (which workers exchange solutions)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum IslandTopology {
+    ///each worker sends its migrants to the next worker
+    Ring,
+    ///each worker sends its migrants to all other workers
+    FullyConnected,
+}
+
+///Which solution of an island migrates and which solution of the receiving island gets replaced by it
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MigrationPolicy {
+    ///the best solution migrates and replaces the worst one
+    BestMigrant,
+    ///a random solution migrates and replaces the worst one
+    RandomMigrant,
+    ///the best solution migrates and replaces the most similar one (distance = jobs to move)
+    DiversityPreserving,
 }
 
 impl Swapper {
@@ -72,29 +115,36 @@ impl Swapper {
             global_bounds,
             config,
             shared_initial_rng,
+            checkpoint: None,
         }
     }
 
-    fn accept_improvement(new_c_max: u32, old_c_max: u32, _concrete_swap_config: &mut ConcreteSwapConfig) -> bool {
-        new_c_max < old_c_max
+    /// the workers record their state at each restart and continue from the resumed state of the checkpoint (if any)
+    pub fn with_checkpoint(mut self, checkpoint: Arc<Checkpoint>, config_id: usize) -> Self {
+        self.checkpoint = Some((checkpoint, config_id));
+        self
+    }
+
+    fn accept_improvement(new_objective: Objective, old_objective: Objective, _concrete_swap_config: &mut ConcreteSwapConfig) -> bool {
+        new_objective < old_objective
     }
 
-    fn accept_decline_by_chance_c(new_c_max: u32, old_c_max: u32, concrete_swap_config: &mut ConcreteSwapConfig) -> bool {
-        Self::accept_decline_by_chance(old_c_max, new_c_max, concrete_swap_config)
+    fn accept_decline_by_chance_c(new_objective: Objective, old_objective: Objective, concrete_swap_config: &mut ConcreteSwapConfig) -> bool {
+        Self::accept_decline_by_chance(old_objective, new_objective, concrete_swap_config)
     }
-    fn accept_decline_by_chance(new_c_max: u32, old_c_max: u32, concrete_swap_config: &mut ConcreteSwapConfig) -> bool {
+    fn accept_decline_by_chance(new_objective: Objective, old_objective: Objective, concrete_swap_config: &mut ConcreteSwapConfig) -> bool {
         let percentage = concrete_swap_config.decline_by_chance_percentage.unwrap() as f64 / 100f64;
         debug_assert!(0f64 <= percentage);
         debug_assert!(1f64 >= percentage);
 
-        if new_c_max < old_c_max {
+        if new_objective < old_objective {
             true
         } else {
             concrete_swap_config.rng.get_mut().gen_bool(percentage)
         }
     }
 
-    fn accept_improvement_or_rs_by_chance_c(_new_c_max: u32, _old_c_max: u32, concrete_swap_config: &mut ConcreteSwapConfig) -> bool {
+    fn accept_improvement_or_rs_by_chance_c(_new_objective: Objective, _old_objective: Objective, concrete_swap_config: &mut ConcreteSwapConfig) -> bool {
         Self::accept_improvement_or_rs_by_chance(concrete_swap_config)
     }
     fn accept_improvement_or_rs_by_chance(concrete_swap_config: &mut ConcreteSwapConfig) -> bool {
@@ -104,33 +154,79 @@ impl Swapper {
         !concrete_swap_config.rng.get_mut().gen_bool(percentage)
     }
 
-    fn accept_all(_new_c_max: u32, _old_c_max: u32, _concrete_swap_config: &mut ConcreteSwapConfig) -> bool {
+    fn accept_all(_new_objective: Objective, _old_objective: Objective, _concrete_swap_config: &mut ConcreteSwapConfig) -> bool {
+        true
+    }
+
+    fn always_best_swap(_concrete_swap_config: &mut ConcreteSwapConfig) -> bool {
         true
     }
 
-    /// swaps jobs of specified good solution(s) to create better one(s)
+    /// same draw as accept_decline_by_chance_c for a swap that doesn't improve
+    fn best_swap_by_decline_chance(concrete_swap_config: &mut ConcreteSwapConfig) -> bool {
+        let percentage = concrete_swap_config.decline_by_chance_percentage.unwrap() as f64 / 100f64;
+        concrete_swap_config.rng.get_mut().gen_bool(percentage)
+    }
+
+    fn accept_late_acceptance(new_objective: Objective, old_objective: Objective, concrete_swap_config: &mut ConcreteSwapConfig) -> bool {
+        let history_length = concrete_swap_config.late_acceptance_history_length.unwrap();
+        if concrete_swap_config.late_acceptance_history.is_empty() {
+            concrete_swap_config.late_acceptance_history = vec![old_objective; history_length];
+        }
+
+        let v = concrete_swap_config.acceptance_iteration % history_length;
+        let accept = new_objective <= old_objective || new_objective <= concrete_swap_config.late_acceptance_history[v];
+        concrete_swap_config.late_acceptance_history[v] = if accept { new_objective } else { old_objective };
+        concrete_swap_config.acceptance_iteration += 1;
+        accept
+    }
+
+    fn accept_threshold_accepting(new_objective: Objective, old_objective: Objective, concrete_swap_config: &mut ConcreteSwapConfig) -> bool {
+        let (_, decrease_percentage) = concrete_swap_config.threshold_accepting_parameters.unwrap();
+        debug_assert!(100 >= decrease_percentage);
+
+        if new_objective < old_objective || new_objective.get_c_max() as f64 <= old_objective.get_c_max() as f64 + concrete_swap_config.threshold {
+            concrete_swap_config.threshold *= 1f64 - decrease_percentage as f64 / 100f64;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn accept_great_deluge(new_objective: Objective, old_objective: Objective, concrete_swap_config: &mut ConcreteSwapConfig) -> bool {
+        let rain_speed = concrete_swap_config.great_deluge_rain_speed.unwrap() as f64 / 1000f64;
+        let water_level = *concrete_swap_config.water_level.get_or_insert(old_objective.get_c_max() as f64);
+
+        if new_objective < old_objective || new_objective.get_c_max() as f64 <= water_level {
+            concrete_swap_config.water_level = Some(water_level - rain_speed);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// swaps jobs of specified good solution(s) to create better one(s) until a termination criterion is reached (runs indefinitely if none is configured)
     /// the newly created solutions get stored in good_solutions
     /// the best one gets returned
-    fn swap(&self, good_solutions: GoodSolutions, args: Arc<Args>, perm: Arc<Permutation>, start_time: Instant) -> Solution {
-        log(format!("running {:?} algorithm...", Swap), false, args.measurement, None);
+    fn swap(&self, good_solutions: GoodSolutions, args: Arc<Args>, start_time: Instant) -> Solution {
+        log(LogLevel::Debug, format!("running {:?} algorithm...", Swap), None);
+
+        let (number_of_solutions, number_of_solutions_to_wait_for) = match self.config.number_of_solutions { //logic when to wait and when not to wait:
+            None => { (current_num_threads(), 1) } //Case: "max"
+            Some(n) => { (n, n) }
+        };
+        log(LogLevel::Debug, String::from("waiting for enough good solutions to run Swap algorithm..."), Some(Swap));
+        if !good_solutions.wait_for_count(number_of_solutions_to_wait_for, Some(Duration::from_secs(args.timeout_after).saturating_sub(start_time.elapsed()))) {
+            return Solution::unsatisfiable(Swap); //timeout
+        }
 
-        rayon::scope(move |s| {
-            let number_of_solutions = match self.config.number_of_solutions { //logic when to wait and when not to wait:
-                None => {
-                    //Case: "max"
-                    while good_solutions.get_solution_count() < 1 {
-                        log(String::from("waiting for enough good solutions to run Swap algorithm..."), false, args.measurement, Some(Swap));
-                    }
-                    current_num_threads()
-                }
-                Some(n) => {
-                    while good_solutions.get_solution_count() < n {
-                        log(String::from("waiting for enough good solutions to run Swap algorithm..."), false, args.measurement, Some(Swap));
-                    }
-                    n
-                }
-            };
+        let swap_start_time = Instant::now();
+        let best_solutions: Mutex<Vec<Solution>> = Mutex::new(vec![]); //best solution of each worker
+        let best_solutions_ref = &best_solutions;
+        let inboxes: Vec<Mutex<Vec<Solution>>> = (0..number_of_solutions).map(|_| Mutex::new(vec![])).collect(); //migrants for each worker (island model)
+        let inboxes_ref = &inboxes;
 
+        rayon::scope(move |s| {
             let mut old_solutions = good_solutions.get_best_solutions(number_of_solutions);
             let mut tmp_i = 0;
             while old_solutions.len() < number_of_solutions { //only happens if number_of_solutions==max and best_solutions.count<max
@@ -142,75 +238,67 @@ impl Swapper {
 
             for i in 0..old_solutions.len() {
                 let old_solutions = Arc::clone(&old_solutions);
-                let perm = Arc::clone(&perm);
                 let args = Arc::clone(&args);
                 let good_solutions = good_solutions.clone();
 
                 s.spawn(move |_| {
-                    //new swap tactics can be added here:
-                    let swap_finding_tactic_fn = match self.config.swap_finding_tactic {
-                        TwoJobBestSwap => Self::find_best_two_job_swap,
-                        TwoJobRandomSwap(_) => Self::find_random_two_job_swap,
-                    };
-
-                    //new swap acceptance rules can be added here:
-                    let swap_acceptance_rule_fn: fn(u32, u32, &mut ConcreteSwapConfig) -> bool = match self.config.swap_acceptance_rule {
-                        Improvement => Self::accept_improvement,
-                        DeclineByChance(_) => Self::accept_decline_by_chance_c,
-                        All => Self::accept_all,
-                        ImprovementOrRsByChance(_) => Self::accept_improvement_or_rs_by_chance_c
-                    };
-                    let random_swap_fails_until_stop = match self.config.swap_finding_tactic {
-                        TwoJobRandomSwap(fails_until_stop) => { Some(fails_until_stop) }
-                        _ => { None }
-                    };
-                    let decline_by_chance_percentage = match self.config.swap_acceptance_rule {
-                        DeclineByChance(percentage) => { Some(percentage) }
-                        _ => { None }
-                    };
-                    let rng = self.shared_initial_rng.lock().unwrap().generate_new_seed().create_rng();
-                    let improvement_or_rs_by_chance_percentage = match self.config.swap_acceptance_rule {
-                        ImprovementOrRsByChance(percentage) => { Some(percentage) }
-                        _ => { None }
+                    let resumed_state = self.checkpoint.as_ref().and_then(|(checkpoint, config_id)| checkpoint.take_resumed_swap_worker(*config_id, i));
+                    //resumed workers continue with their own rngs => the shared initial rng stays where the checkpoint left it
+                    let (rng, rf_rng) = match &resumed_state {
+                        Some(state) => { (state.rng.clone(), state.rf_rng.clone()) }
+                        None => {
+                            let mut shared_initial_rng = self.shared_initial_rng.lock().unwrap();
+                            (shared_initial_rng.generate_new_seed().create_rng(), shared_initial_rng.generate_new_seed().create_rng())
+                        }
                     };
-
-                    let mut concrete_swap_config = ConcreteSwapConfig {
-                        swap_finding_tactic: swap_finding_tactic_fn,
-                        swap_acceptance_rule: swap_acceptance_rule_fn,
-                        decline_by_chance_percentage,
-                        random_swap_fails_until_stop,
-                        rng,
-                        improvement_or_rs_by_chance_percentage,
+                    //the time budget continues where the checkpointed worker left off:
+                    let worker_start_time = match &resumed_state {
+                        Some(state) => { Instant::now().checked_sub(state.swap_elapsed).unwrap_or(swap_start_time) }
+                        None => { swap_start_time }
                     };
+                    let mut concrete_swap_config = ConcreteSwapConfig::new(&self.config, rng);
 
                     let mut solution = old_solutions[i].clone();
                     solution.add_algorithm(Swap);
-                    solution.add_config(format!("SWAP_CONFIG: SWAP_FINDING_TACTIC:{:?}; SWAP_ACCEPTANCE_RULE:{:?}; NUMBER_OF_SOLUTIONS:{:?}; RNG:{}", self.config.swap_finding_tactic, self.config.swap_acceptance_rule, self.config.number_of_solutions, concrete_swap_config.rng));
+                    solution.add_config(format!("SWAP_CONFIG: SWAP_FINDING_TACTIC:{:?}; SWAP_ACCEPTANCE_RULE:{:?}; OBJECTIVE:{:?}; NUMBER_OF_SOLUTIONS:{:?}; ISLAND_TOPOLOGY:{:?}; MIGRATION_INTERVAL:{}; MIGRATION_POLICY:{:?}; ISLAND_SIZE:{}; RNG:{}", self.config.swap_finding_tactic, self.config.swap_acceptance_rule, self.config.objective_kind, self.config.number_of_solutions, self.config.island_topology, self.config.migration_interval, self.config.migration_policy, self.config.island_size, concrete_swap_config.rng));
 
                     let mut restart_after_steps = self.config.restart_after_steps.unwrap();
                     let mut restart_possibility = self.config.restart_possibility.unwrap();
 
-                    let mut rf_scheduler = RFScheduler::new(Arc::clone(&self.input), Arc::clone(&self.global_bounds), &RFConfig::new(), Arc::clone(&self.shared_initial_rng), Some(Swap));
+                    let mut rf_scheduler = RFScheduler::with_rng(Arc::clone(&self.input), Arc::clone(&self.global_bounds), &RFConfig::new(), rf_rng, Some(Swap));
 
-                    let keep_sorted = self.config.swap_finding_tactic == TwoJobBestSwap;
+                    let keep_sorted = self.keeps_jobs_sorted();
 
                     let mut map: BTreeMap<u32, Solution> = BTreeMap::new();
 
+                    let mut worker_best_solution = solution.clone();
+                    let mut island = vec![solution.clone()]; //own sub-population (island model), sorted ascending by c_max
+                    let (mut total_steps, mut restarts, mut restarts_without_improvement) = (0, 0, 0);
+
+                    if let Some(state) = resumed_state {
+                        //continue where the checkpointed worker left off:
+                        (solution, worker_best_solution, island) = (state.solution, state.best_solution, state.island);
+                        (restart_after_steps, restart_possibility) = (state.restart_after_steps, state.restart_possibility);
+                        (total_steps, restarts, restarts_without_improvement) = (state.total_steps, state.restarts, state.restarts_without_improvement);
+                        map = state.unflushed_solutions.into_iter().map(|unflushed_solution| (unflushed_solution.get_data().get_c_max(), unflushed_solution)).collect();
+                    }
+
                     loop {
                         let mut restart = false;
                         let mut steps = 0;
+                        concrete_swap_config.reset_acceptance_state();
                         if keep_sorted {
                             solution.get_mut_data().get_mut_machine_jobs().sort_jobs();
                         }
                         let mut curr_best_solution = solution.clone();
                         let mut curr_best_c_max = curr_best_solution.get_data().get_c_max();
-                        while let Some(swap_indices) = (concrete_swap_config.swap_finding_tactic)(self, &solution, &mut concrete_swap_config) {
-                            solution.swap_jobs(swap_indices, self.input.get_jobs(), keep_sorted);
+                        while (concrete_swap_config.swap_finding_tactic)(self, &mut solution, &mut concrete_swap_config, keep_sorted) {
                             //add newly found solution to shared structs
-                            //self.global_bounds.update_upper_bound(solution.get_data().get_c_max(), &solutls -ion, Arc::clone(&args), Arc::clone(&perm), start_time, Some(Swap), self.input.get_jobs(), self.input.get_machine_count()); //TODO falls es jetzt schon skaliert kann man das hier drinn lassen. ansonsten evtl auch nur bei restart machen (dann sollte man aber evtl immer die beste solution und die letzte speichern und bei restart weiter geben)
+                            //self.global_bounds.update_upper_bound(solution.get_data().get_c_max(), &solutls -ion, Arc::clone(&args), start_time, Some(Swap)); //TODO falls es jetzt schon skaliert kann man das hier drinn lassen. ansonsten evtl auch nur bei restart machen (dann sollte man aber evtl immer die beste solution und die letzte speichern und bei restart weiter geben)
                             //good_solutions.add_solution(solution.clone()); // das nur lokal halten jeweils oder ganz raus...
                             //println!("swap");
                             steps += 1;
+                            total_steps += 1;
                             //println!("{}", steps);
 
                             if self.config.do_restart_after_steps {
@@ -233,31 +321,70 @@ impl Swapper {
                                 curr_best_solution = solution.clone();
                                 curr_best_c_max = curr_c_max;
                             }
+
+                            if self.config.termination_reached(total_steps, restarts, restarts_without_improvement, curr_best_c_max, worker_start_time) || self.global_bounds.get_cancellation_token().is_cancelled() { break; }
                         }
                         //println!("DO RESTART");
 
+                        if curr_best_c_max < worker_best_solution.get_data().get_c_max() {
+                            worker_best_solution = curr_best_solution.clone();
+                            restarts_without_improvement = 0;
+                        } else {
+                            restarts_without_improvement += 1;
+                        }
+
+                        if self.config.island_topology.is_some() {
+                            self.integrate_into_island(&mut island, curr_best_solution.clone());
+                        }
+
                         map.insert(solution.get_data().get_c_max(), solution); //todo evtl cmax eq entfernen
                         map.insert(curr_best_c_max, curr_best_solution);
                         if map.len() > 100 {
                             for _j in 0..10 {
-                                let (c, s) = map.pop_first().unwrap();
-                                self.global_bounds.update_upper_bound(c, &s, Arc::clone(&args), Arc::clone(&perm), start_time, Some(Swap), self.input.get_jobs(), self.input.get_machine_count());
+                                let (c, mut s) = map.pop_first().unwrap();
+                                self.global_bounds.update_upper_bound(c, &mut s, Arc::clone(&args), start_time, Some(Swap));
                                 good_solutions.add_solution(s);
                             }
                             map.clear();
                         }
 
-                        /*self.global_bounds.update_upper_bound(curr_best_c_max, &curr_best_solution, Arc::clone(&args), Arc::clone(&perm), start_time, Some(Swap), self.input.get_jobs(), self.input.get_machine_count());
+                        if self.config.termination_reached(total_steps, restarts, restarts_without_improvement, worker_best_solution.get_data().get_c_max(), worker_start_time) || self.global_bounds.get_cancellation_token().is_cancelled() {
+                            //store all remaining solutions:
+                            for (c, mut s) in map {
+                                self.global_bounds.update_upper_bound(c, &mut s, Arc::clone(&args), start_time, Some(Swap));
+                                good_solutions.add_solution(s);
+                            }
+                            best_solutions_ref.lock().unwrap().push(worker_best_solution);
+                            break;
+                        }
+                        restarts += 1;
+
+                        if let Some(island_topology) = self.config.island_topology {
+                            let migrants: Vec<Solution> = inboxes_ref[i].lock().unwrap().drain(..).collect();
+                            for migrant in migrants {
+                                self.integrate_into_island(&mut island, migrant);
+                            }
+                            if restarts % self.config.migration_interval == 0 {
+                                self.migrate(i, island_topology, &island, inboxes_ref, &mut concrete_swap_config);
+                            }
+                        }
+
+                        /*self.global_bounds.update_upper_bound(curr_best_c_max, &curr_best_solution, Arc::clone(&args), start_time, Some(Swap));
                         good_solutions.add_solution(curr_best_solution);
 
-                        self.global_bounds.update_upper_bound(solution.get_data().get_c_max(), &solution, Arc::clone(&args), Arc::clone(&perm), start_time, Some(Swap), self.input.get_jobs(), self.input.get_machine_count());
+                        self.global_bounds.update_upper_bound(solution.get_data().get_c_max(), &solution, Arc::clone(&args), start_time, Some(Swap));
                         good_solutions.add_solution(solution); */
 
                         let random_restart = concrete_swap_config.rng.get_mut().gen_bool(self.config.random_restart_possibility);
 
                         if random_restart {
                             //generate new random solution:
-                            solution = rf_scheduler.schedule_without_bounds(Arc::clone(&args), Arc::clone(&perm), start_time);
+                            solution = rf_scheduler.schedule_without_bounds(Arc::clone(&args), start_time);
+                        } else if self.config.island_topology.is_some() {
+                            //choose x-th solution of the own island (using exp. distribution):
+                            let exp = Exp::new(self.config.lambda).unwrap();
+                            let x = min(exp.sample(concrete_swap_config.rng.get_mut()) as usize, island.len() - 1);
+                            solution = island[x].clone();
                         } else {
                             //choose x-th good solution (using exp. distribution):
                             let exp = Exp::new(self.config.lambda).unwrap();
@@ -266,13 +393,193 @@ impl Swapper {
                         }
 
                         solution.add_algorithm(Swap);
-                        solution.add_config(format!("SWAP_CONFIG: SWAP_FINDING_TACTIC:{:?}; SWAP_ACCEPTANCE_RULE:{:?}; NUMBER_OF_SOLUTIONS:{:?}; RNG:{}", self.config.swap_finding_tactic, self.config.swap_acceptance_rule, self.config.number_of_solutions, concrete_swap_config.rng));
+                        solution.add_config(format!("SWAP_CONFIG: SWAP_FINDING_TACTIC:{:?}; SWAP_ACCEPTANCE_RULE:{:?}; OBJECTIVE:{:?}; NUMBER_OF_SOLUTIONS:{:?}; ISLAND_TOPOLOGY:{:?}; MIGRATION_INTERVAL:{}; MIGRATION_POLICY:{:?}; ISLAND_SIZE:{}; RNG:{}", self.config.swap_finding_tactic, self.config.swap_acceptance_rule, self.config.objective_kind, self.config.number_of_solutions, self.config.island_topology, self.config.migration_interval, self.config.migration_policy, self.config.island_size, concrete_swap_config.rng));
+
+                        //only needed if checkpoints get written (--checkpoint):
+                        if let Some((checkpoint, config_id)) = self.checkpoint.as_ref().filter(|_| args.checkpoint.is_some()) {
+                            checkpoint.update_swap_worker(*config_id, i, SwapWorkerState {
+                                solution: solution.clone(),
+                                best_solution: worker_best_solution.clone(),
+                                island: island.clone(),
+                                restart_after_steps,
+                                restart_possibility,
+                                total_steps,
+                                restarts,
+                                restarts_without_improvement,
+                                rng: concrete_swap_config.rng.clone(),
+                                rf_rng: rf_scheduler.get_rng().clone(),
+                                swap_elapsed: worker_start_time.elapsed(),
+                                unflushed_solutions: map.values().cloned().collect(),
+                            }, good_solutions_to_string(&good_solutions, &self.global_bounds, &args.rng_seed, &self.input));
+                        }
                     }
                 });
             }
         });
 
-        Solution::unsatisfiable(Swap) //not reachable
+        //the run continues without the Swap workers (if cancelled, the last checkpoint has to stay consistent with the worker states):
+        if let Some((checkpoint, _)) = self.checkpoint.as_ref().filter(|_| !self.global_bounds.get_cancellation_token().is_cancelled()) {
+            checkpoint.clear_good_solutions_snapshot();
+        }
+
+        match best_solutions.into_inner().unwrap().into_iter().min_by_key(|solution| solution.get_data().get_c_max()) {
+            None => { Solution::unsatisfiable(Swap) }
+            Some(best_solution) => { best_solution }
+        }
+    }
+
+    /// adds the solution to the island (if it is not already part of it) by replacing the worst/most similar solution (depending on the migration policy) if the island is full
+    fn integrate_into_island(&self, island: &mut Vec<Solution>, solution: Solution) {
+        let jobs = self.input.get_jobs();
+        let machine_jobs = solution.get_data().get_machine_jobs();
+
+        let replace_index = if self.config.migration_policy == DiversityPreserving {
+            let distances: Vec<usize> = island.iter().map(|island_solution| island_solution.get_data().get_machine_jobs().distance(machine_jobs, jobs)).collect();
+            if distances.contains(&0) {
+                return; //already part of the island
+            }
+            (0..island.len()).min_by_key(|&index| distances[index]).unwrap()
+        } else {
+            if island.iter().any(|island_solution| island_solution.get_data().get_machine_jobs() == machine_jobs) {
+                return; //already part of the island
+            }
+            island.len() - 1
+        };
+
+        if island.len() < self.config.island_size {
+            island.push(solution);
+        } else if solution.get_data().get_c_max() <= island[replace_index].get_data().get_c_max() {
+            island[replace_index] = solution;
+        } else {
+            return;
+        }
+        island.sort_by_key(|island_solution| island_solution.get_data().get_c_max());
+    }
+
+    /// sends a migrant (depending on the migration policy) to the neighbors of the worker (depending on the topology)
+    fn migrate(&self, worker_index: usize, island_topology: IslandTopology, island: &[Solution], inboxes: &[Mutex<Vec<Solution>>], concrete_swap_config: &mut ConcreteSwapConfig) {
+        let migrant = match self.config.migration_policy {
+            BestMigrant | DiversityPreserving => { &island[0] }
+            RandomMigrant => { &island[concrete_swap_config.rng.get_mut().gen_range(0..island.len())] }
+        };
+        let neighbors: Vec<usize> = match island_topology {
+            Ring => { vec![(worker_index + 1) % inboxes.len()] }
+            FullyConnected => { (0..inboxes.len()).collect() }
+        };
+        for neighbor in neighbors.into_iter().filter(|&neighbor| neighbor != worker_index) {
+            inboxes[neighbor].lock().unwrap().push(migrant.clone());
+        }
+    }
+
+    /// runs the swap tactic on the solution until no swap is found or max_steps swaps were done
+    /// returns the best solution found on the way
+    pub fn local_search(&self, mut solution: Solution, concrete_swap_config: &mut ConcreteSwapConfig, max_steps: usize) -> Solution {
+        let keep_sorted = self.keeps_jobs_sorted();
+        concrete_swap_config.reset_acceptance_state();
+        if keep_sorted {
+            solution.get_mut_data().get_mut_machine_jobs().sort_jobs();
+        }
+        let mut best_solution = solution.clone();
+        let mut steps = 0;
+        while steps < max_steps && (concrete_swap_config.swap_finding_tactic)(self, &mut solution, concrete_swap_config, keep_sorted) {
+            steps += 1;
+            if solution.get_data().get_c_max() < best_solution.get_data().get_c_max() {
+                best_solution = solution.clone();
+            }
+        }
+        best_solution
+    }
+
+    /// the jobs on each machine need to be sorted (descending by length) for some swap tactics
+    fn keeps_jobs_sorted(&self) -> bool {
+        self.config.swap_finding_tactic == TwoJobBestSwap
+    }
+
+    fn best_two_job_swap_step(&self, solution: &mut Solution, concrete_swap_config: &mut ConcreteSwapConfig, keep_sorted: bool) -> bool {
+        let swap_indices = self.find_best_two_job_swap(solution, concrete_swap_config);
+        self.do_swap(solution, swap_indices, keep_sorted)
+    }
+
+    fn random_two_job_swap_step(&self, solution: &mut Solution, concrete_swap_config: &mut ConcreteSwapConfig, keep_sorted: bool) -> bool {
+        let swap_indices = self.find_random_two_job_swap(solution, concrete_swap_config);
+        self.do_swap(solution, swap_indices, keep_sorted)
+    }
+
+    fn all_pairs_best_swap_step(&self, solution: &mut Solution, concrete_swap_config: &mut ConcreteSwapConfig, keep_sorted: bool) -> bool {
+        let swap_indices = self.find_all_pairs_two_job_swap(solution, concrete_swap_config, false);
+        self.do_swap(solution, swap_indices, keep_sorted)
+    }
+
+    fn all_pairs_first_swap_step(&self, solution: &mut Solution, concrete_swap_config: &mut ConcreteSwapConfig, keep_sorted: bool) -> bool {
+        let swap_indices = self.find_all_pairs_two_job_swap(solution, concrete_swap_config, true);
+        self.do_swap(solution, swap_indices, keep_sorted)
+    }
+
+    /// performs the swap (if one was found) and returns whether it was performed
+    fn do_swap(&self, solution: &mut Solution, swap_indices: Option<(usize, usize, usize, i32)>, keep_sorted: bool) -> bool {
+        match swap_indices {
+            None => { false }
+            Some(swap_indices) => {
+                solution.swap_jobs(swap_indices, self.input.get_jobs(), keep_sorted);
+                true
+            }
+        }
+    }
+
+    /// optimally redistributes the jobs of the heaviest machine and the lightest partner machine whose redistribution lowers the workload of the heaviest machine
+    /// (this subsumes all 2 job swaps and pushes between the two machines), the redistribution has to be accepted by the swap acceptance rule
+    fn pair_reoptimization_step(&self, solution: &mut Solution, concrete_swap_config: &mut ConcreteSwapConfig, keep_sorted: bool) -> bool {
+        let jobs = self.input.get_jobs();
+        let machine_jobs = solution.get_data().get_machine_jobs();
+        let current_objective = Objective::new(concrete_swap_config.objective_kind.with_plateau_tie_breaker(), machine_jobs);
+        let heaviest_machine_index = machine_jobs.get_heaviest_machine_index();
+        let heaviest_machine_workload = machine_jobs.get_machine_workload(heaviest_machine_index);
+
+        let mut partners: Vec<usize> = (0..self.input.get_machine_count()).filter(|&m| m != heaviest_machine_index).collect();
+        partners.sort_by_key(|&m| machine_jobs.get_machine_workload(m));
+
+        for partner_index in partners {
+            let partner_workload = machine_jobs.get_machine_workload(partner_index);
+            if heaviest_machine_workload - partner_workload < 2 {
+                return false; //all remaining partners can't be balanced any better
+            }
+
+            let pair_jobs: Vec<usize> = machine_jobs.get_machine_jobs(heaviest_machine_index).iter().chain(machine_jobs.get_machine_jobs(partner_index).iter()).copied().collect();
+            let pair_job_lengths: Vec<u32> = pair_jobs.iter().map(|&job_index| jobs[job_index]).collect();
+            let pair_workload = heaviest_machine_workload + partner_workload;
+
+            //pruning: the moved workload d (0 < d < workload difference) is a multiple of the gcd of the job lengths
+            if lengths_gcd(&pair_job_lengths) >= heaviest_machine_workload - partner_workload {
+                continue;
+            }
+
+            //the smaller part goes on the partner machine:
+            let subset = max_subset_sum(&pair_job_lengths, pair_workload / 2);
+            let subset_workload: u32 = subset.iter().map(|&i| pair_job_lengths[i]).sum();
+            if pair_workload - subset_workload < heaviest_machine_workload {
+                let new_objective = self.simulate_workload_transfer(heaviest_machine_index, partner_index, (subset_workload - partner_workload) as i64, machine_jobs, &current_objective);
+                if !(concrete_swap_config.swap_acceptance_rule)(new_objective, current_objective, concrete_swap_config) {
+                    return false;
+                }
+
+                let mut in_subset = vec![false; pair_jobs.len()];
+                for &i in &subset {
+                    in_subset[i] = true;
+                }
+                let (mut partner_jobs, mut heaviest_jobs) = (vec![], vec![]);
+                for (i, &job_index) in pair_jobs.iter().enumerate() {
+                    if in_subset[i] { partner_jobs.push(job_index); } else { heaviest_jobs.push(job_index); }
+                }
+                if keep_sorted {
+                    partner_jobs.sort_by(|a, b| b.cmp(a));
+                    heaviest_jobs.sort_by(|a, b| b.cmp(a));
+                }
+                solution.set_machine_jobs(partner_index, partner_jobs, jobs);
+                solution.set_machine_jobs(heaviest_machine_index, heaviest_jobs, jobs);
+                return true;
+            }
+        }
+        false
     }
 
     /// 2 job swap brute force (try all possible swaps)
@@ -283,8 +590,8 @@ impl Swapper {
         if false {
 
             //-----------old version--------------------
-            let mut current_c_max = solution.get_data().get_c_max();
-            let current_heaviest_machines = solution.get_data().get_machine_jobs().get_machines_with_workload(current_c_max);
+            let mut current_objective = Objective::new(concrete_swap_config.objective_kind, machine_jobs);
+            let current_heaviest_machines = solution.get_data().get_machine_jobs().get_machines_with_workload(current_objective.get_c_max());
             let mut swap_found = false;
             let m1 = current_heaviest_machines[0];
             for m2 in 0..self.input.get_machine_count() {
@@ -295,10 +602,10 @@ impl Swapper {
                 for j1 in 0..machine_1_jobs.len() {
                     for j2 in 0..machine_2_jobs.len() as i32 { //quadratische laufzeit
                         //for all job pairs (j1,j2) on (m1,m2)
-                        let new_c_max = self.simulate_two_job_swap(m1, machine_1_jobs[j1], m2, machine_2_jobs[j2 as usize], machine_jobs, current_heaviest_machines.as_slice());
-                        if (concrete_swap_config.swap_acceptance_rule)(new_c_max, current_c_max, concrete_swap_config) {
+                        let new_objective = self.simulate_two_job_swap(m1, machine_1_jobs[j1], m2, machine_2_jobs[j2 as usize], machine_jobs, &current_objective);
+                        if (concrete_swap_config.swap_acceptance_rule)(new_objective, current_objective, concrete_swap_config) {
                             swap_found = true;
-                            current_c_max = new_c_max;
+                            current_objective = new_objective;
                             swap_indices = (m1, j1, m2, j2);
                         }
                     }
@@ -313,7 +620,7 @@ impl Swapper {
         } else {
 
             //-----------NEW version--------------------
-            if (concrete_swap_config.swap_acceptance_rule)(1, 2, concrete_swap_config) { //to determine whether the best swap needs to be computed or not
+            if (concrete_swap_config.needs_best_swap)(concrete_swap_config) { //to determine whether the best swap needs to be computed or not
                 //swap will be accepted, sow e compute it:
 
                 //println!("{:?}", machine_jobs);
@@ -338,7 +645,7 @@ impl Swapper {
 
                     if diff < 1 {
                         if pointer_h_m == heaviest_machine_jobs_indices.len() - 1 {
-                            return None;
+                            return self.find_best_plateau_swap(solution, concrete_swap_config);
                         }
                         pointer_h_m += 1;
                     } else if diff <= max_diff {
@@ -356,7 +663,7 @@ impl Swapper {
                         }
                     } else {
                         if pointer_l_m == lightest_machine_jobs_indices.len() - 1 {
-                            return None;
+                            return self.find_best_plateau_swap(solution, concrete_swap_config);
                         }
                         pointer_l_m += 1;
                     }
@@ -371,14 +678,131 @@ impl Swapper {
         }
     }
 
+    /// 2 job swaps (and pushes) between every machine with workload c_max and every partner machine
+    /// the best (or first) improving swap gets passed to the swap acceptance rule, if there is none the best non improving one (the rule may accept it)
+    fn find_all_pairs_two_job_swap(&self, solution: &Solution, concrete_swap_config: &mut ConcreteSwapConfig, first_improvement: bool) -> Option<(usize, usize, usize, i32)> {
+        let machine_jobs = solution.get_data().get_machine_jobs();
+        let current_objective = Objective::new(concrete_swap_config.objective_kind.with_plateau_tie_breaker(), machine_jobs);
+        let critical_machines = machine_jobs.get_machines_with_workload(current_objective.get_c_max());
+
+        let best_swap = self.find_critical_machine_swap(&critical_machines, machine_jobs, &current_objective, first_improvement, true)
+            .or_else(|| self.find_critical_machine_swap(&critical_machines, machine_jobs, &current_objective, false, false));
+
+        match best_swap {
+            Some((new_objective, swap_indices)) if (concrete_swap_config.swap_acceptance_rule)(new_objective, current_objective, concrete_swap_config) => { Some(swap_indices) }
+            _ => { None }
+        }
+    }
+
+    /// best (or first) 2 job swap (or push) between one of the critical machines and any partner machine (None if there is no (improving) swap)
+    /// improving swaps need a partner whose workload is at least 2 lower (all others get skipped), for many partners they get evaluated in parallel
+    fn find_critical_machine_swap(&self, critical_machines: &[usize], machine_jobs: &MachineJobs, current_objective: &Objective, first_improvement: bool, only_improving: bool) -> Option<(Objective, (usize, usize, usize, i32))> {
+        let jobs = self.input.get_jobs();
+
+        let mut best_swap: Option<(Objective, (usize, usize, usize, i32))> = None;
+        for &m1 in critical_machines {
+            let machine_1_workload = machine_jobs.get_machine_workload(m1);
+            let machine_1_jobs = machine_jobs.get_machine_jobs(m1);
+
+            //pruning: only partners with a workload difference >= 2 can improve (sorted ascending => the most promising ones first)
+            let mut partners: Vec<usize> = (0..self.input.get_machine_count()).filter(|&m2| m2 != m1 && (!only_improving || machine_1_workload - machine_jobs.get_machine_workload(m2) >= 2)).collect();
+            partners.sort_by_key(|&m2| machine_jobs.get_machine_workload(m2));
+
+            //best (or first) swap between m1 and m2:
+            let evaluate_partner = |&m2: &usize| -> Option<(Objective, (usize, usize, usize, i32))> {
+                let workload_difference = (machine_1_workload - machine_jobs.get_machine_workload(m2)) as i64;
+                let machine_2_jobs = machine_jobs.get_machine_jobs(m2);
+                let mut pair_best_swap: Option<(Objective, (usize, usize, usize, i32))> = None;
+                for (j1, &job_1_index) in machine_1_jobs.iter().enumerate() {
+                    //j2 == -1 => push
+                    for j2 in -1..machine_2_jobs.len() as i32 {
+                        let job_2_length = if j2 == -1 { 0 } else { jobs[machine_2_jobs[j2 as usize]] };
+                        let transferred_workload = jobs[job_1_index] as i64 - job_2_length as i64;
+                        //improving swaps move a workload d with 0 < d < workload_difference
+                        if transferred_workload == 0 || (only_improving && (transferred_workload < 0 || transferred_workload >= workload_difference)) {
+                            continue;
+                        }
+                        let new_objective = self.simulate_workload_transfer(m1, m2, transferred_workload, machine_jobs, current_objective);
+                        let is_better = match pair_best_swap {
+                            None => { !only_improving || new_objective < *current_objective }
+                            Some((best_objective, _)) => { new_objective < best_objective }
+                        };
+                        if is_better {
+                            pair_best_swap = Some((new_objective, (m1, j1, m2, j2)));
+                            if first_improvement {
+                                return pair_best_swap;
+                            }
+                        }
+                    }
+                }
+                pair_best_swap
+            };
+
+            let swap = match (first_improvement, partners.len() >= PARALLEL_PARTNER_EVALUATION_THRESHOLD) {
+                (true, true) => { partners.par_iter().find_map_first(evaluate_partner) }
+                (true, false) => { partners.iter().find_map(evaluate_partner) }
+                (false, true) => { partners.par_iter().filter_map(evaluate_partner).min_by_key(|&(objective, _)| objective) }
+                (false, false) => { partners.iter().filter_map(evaluate_partner).min_by_key(|&(objective, _)| objective) }
+            };
+
+            if let Some((new_objective, swap_indices)) = swap {
+                if best_swap.is_none_or(|(best_objective, _)| new_objective < best_objective) {
+                    best_swap = Some((new_objective, swap_indices));
+                    if first_improvement {
+                        break;
+                    }
+                }
+            }
+        }
+        best_swap
+    }
+
+    /// fallback of the 2 job best swap if c_max can't be improved (only used with secondary objectives or rules accepting worsening swaps):
+    /// best swap between a machine with workload c_max and any other machine that improves the objective
+    /// rules that may accept worsening swaps (late acceptance, threshold accepting, great deluge) get the best non improving swap of all pairs instead
+    fn find_best_plateau_swap(&self, solution: &Solution, concrete_swap_config: &mut ConcreteSwapConfig) -> Option<(usize, usize, usize, i32)> {
+        if concrete_swap_config.accepts_worsening_swaps {
+            return self.find_all_pairs_two_job_swap(solution, concrete_swap_config, false);
+        }
+        if concrete_swap_config.objective_kind == CMax {
+            return None;
+        }
+        let jobs = self.input.get_jobs();
+        let machine_jobs = solution.get_data().get_machine_jobs();
+        let current_objective = Objective::new(concrete_swap_config.objective_kind, machine_jobs);
+
+        let mut best_swap: Option<(Objective, (usize, usize, usize, i32))> = None;
+        for m1 in machine_jobs.get_machines_with_workload(current_objective.get_c_max()) {
+            let machine_1_jobs = machine_jobs.get_machine_jobs(m1);
+            for m2 in (0..self.input.get_machine_count()).filter(|&m2| m2 != m1) {
+                let machine_2_jobs = machine_jobs.get_machine_jobs(m2);
+                for (j1, &job_1_index) in machine_1_jobs.iter().enumerate() {
+                    for (j2, &job_2_index) in machine_2_jobs.iter().enumerate() {
+                        if jobs[job_1_index] <= jobs[job_2_index] {
+                            continue; //the critical machine would not get lighter
+                        }
+                        let new_objective = self.simulate_two_job_swap(m1, job_1_index, m2, job_2_index, machine_jobs, &current_objective);
+                        if new_objective < best_swap.map_or(current_objective, |(best_objective, _)| best_objective) {
+                            best_swap = Some((new_objective, (m1, j1, m2, j2 as i32)));
+                        }
+                    }
+                }
+            }
+        }
+
+        match best_swap {
+            Some((new_objective, swap_indices)) if (concrete_swap_config.swap_acceptance_rule)(new_objective, current_objective, concrete_swap_config) => { Some(swap_indices) }
+            _ => { None }
+        }
+    }
+
     /// 2 job random swap
     fn find_random_two_job_swap(&self, solution: &Solution, concrete_swap_config: &mut ConcreteSwapConfig) -> Option<(usize, usize, usize, i32)> {
         let fails_until_stop = concrete_swap_config.random_swap_fails_until_stop.unwrap();
         let mut fails: usize = 0;
         let machine_count = self.input.get_machine_count();
         let machine_jobs = solution.get_data().get_machine_jobs();
-        let current_c_max = solution.get_data().get_c_max();
-        let current_heaviest_machines = solution.get_data().get_machine_jobs().get_machines_with_workload(current_c_max);
+        let current_objective = Objective::new(concrete_swap_config.objective_kind, machine_jobs);
 
         loop {
             //generate random values
@@ -400,15 +824,15 @@ impl Swapper {
             let j2 = concrete_swap_config.rng.get_mut().gen_range(0..machine_2_jobs.len());
 
             //check swap
-            let new_c_max = self.simulate_two_job_swap(
+            let new_objective = self.simulate_two_job_swap(
                 m1,
                 machine_1_jobs[j1],
                 m2,
                 machine_2_jobs[j2],
                 machine_jobs,
-                current_heaviest_machines.as_slice(),
+                &current_objective,
             );
-            if (concrete_swap_config.swap_acceptance_rule)(new_c_max, current_c_max, concrete_swap_config) {
+            if (concrete_swap_config.swap_acceptance_rule)(new_objective, current_objective, concrete_swap_config) {
                 return Some((m1, j1, m2, j2 as i32));
             } else {
                 fails += 1;
@@ -448,20 +872,32 @@ impl Swapper {
         (m1, j1, m2, j2 as i32)
     }
 
-    ///computes the c_max that the current solution would have after a specified swap
-    fn simulate_two_job_swap(&self, machine_1_index: usize, job_1_index: usize, machine_2_index: usize, job_2_index: usize, machine_jobs: &MachineJobs, current_heaviest_machines: &[usize]) -> u32 {
+    ///computes the objective that the current solution would have after a specified swap
+    fn simulate_two_job_swap(&self, machine_1_index: usize, job_1_index: usize, machine_2_index: usize, job_2_index: usize, machine_jobs: &MachineJobs, current_objective: &Objective) -> Objective {
         let jobs = self.input.get_jobs();
+        self.simulate_workload_transfer(machine_1_index, machine_2_index, jobs[job_1_index] as i64 - jobs[job_2_index] as i64, machine_jobs, current_objective)
+    }
 
-        let machine_1_swap_workload = machine_jobs.get_machine_workload(machine_1_index) + jobs[job_2_index] - jobs[job_1_index];
-        let machine_2_swap_workload = machine_jobs.get_machine_workload(machine_2_index) + jobs[job_1_index] - jobs[job_2_index];
-        let max_workload = max(machine_1_swap_workload, machine_2_swap_workload);
-
-        if current_heaviest_machines.iter().any(|&machine| machine != machine_1_index && machine != machine_2_index) {
-            let current_c_max = machine_jobs.get_machine_workload(current_heaviest_machines[0]);
-            max(current_c_max, max_workload)
-        } else {
-            max_workload
-        }
+    ///computes the objective that the current solution would have after moving the given workload from machine 1 to machine 2 (negative => from machine 2 to machine 1)
+    fn simulate_workload_transfer(&self, machine_1_index: usize, machine_2_index: usize, transferred_workload: i64, machine_jobs: &MachineJobs, current_objective: &Objective) -> Objective {
+        let (machine_1_workload, machine_2_workload) = (machine_jobs.get_machine_workload(machine_1_index), machine_jobs.get_machine_workload(machine_2_index));
+        let machine_1_swap_workload = (machine_1_workload as i64 - transferred_workload) as u32;
+        let machine_2_swap_workload = (machine_2_workload as i64 + transferred_workload) as u32;
+        let c_max = max(machine_jobs.get_max_workload_without(&[machine_1_index, machine_2_index]), max(machine_1_swap_workload, machine_2_swap_workload));
+
+        //only the values that get compared:
+        let kind = current_objective.get_kind();
+        let critical_machines = match kind.uses_critical_machines() {
+            true => { machine_jobs.count_machines_with_workload_without(c_max, &[machine_1_index, machine_2_index]) + (machine_1_swap_workload == c_max) as usize + (machine_2_swap_workload == c_max) as usize }
+            false => { 0 }
+        };
+        let square = |workload: u32| workload as u64 * workload as u64;
+        let squared_workload_sum = match kind.uses_squared_workload_sum() {
+            true => { current_objective.get_squared_workload_sum() + square(machine_1_swap_workload) + square(machine_2_swap_workload) - square(machine_1_workload) - square(machine_2_workload) }
+            false => { 0 }
+        };
+
+        current_objective.with_values(c_max, critical_machines, squared_workload_sum)
     }
 }
 
@@ -469,6 +905,9 @@ impl SwapTactic {
     pub fn from_str(input: &str) -> Result<Self, String> {
         match input {
             "two-job-best-swap" => Ok(TwoJobBestSwap),
+            "pair-reoptimization" => Ok(PairReoptimization),
+            "all-pairs-best-swap" => Ok(AllPairsBestSwap),
+            "all-pairs-first-swap" => Ok(AllPairsFirstSwap),
             "two-job-random-swap" => {
                 //default:
                 Ok(TwoJobRandomSwap(50))
@@ -488,11 +927,44 @@ impl SwapTactic {
     }
 }
 
+impl IslandTopology {
+    pub fn from_str(input: &str) -> Result<Self, String> {
+        match input {
+            "ring" => Ok(Ring),
+            "full" => Ok(FullyConnected),
+            _ => Err(format!("invalid variant: {input}"))
+        }
+    }
+}
+
+impl MigrationPolicy {
+    pub fn from_str(input: &str) -> Result<Self, String> {
+        match input {
+            "best" => Ok(BestMigrant),
+            "random" => Ok(RandomMigrant),
+            "diversity" => Ok(DiversityPreserving),
+            _ => Err(format!("invalid variant: {input}"))
+        }
+    }
+}
+
 impl SwapAcceptanceRule {
     pub fn from_str(input: &str) -> Result<Self, String> {
         match input {
             "improvement" => Ok(Improvement),
             "all" => Ok(All),
+            "late-acceptance" => {
+                //default:
+                Ok(LateAcceptance(100))
+            }
+            "threshold-accepting" => {
+                //default:
+                Ok(ThresholdAccepting(50, 1))
+            }
+            "great-deluge" => {
+                //default:
+                Ok(GreatDeluge(100))
+            }
             _ => {
                 //more complex param (probably)
                 if Regex::new(r"^decline-by-([0-9]|[1-9][0-9]|100)%-chance$").unwrap().is_match(input) {
@@ -501,6 +973,13 @@ impl SwapAcceptanceRule {
                 } else if Regex::new(r"^improvement-or-rs-by-([0-9]|[1-9][0-9]|100)%-chance$").unwrap().is_match(input) { //TODO testen ob es funzt!
                     let p = atoi::<u8>(&input.as_bytes()[21..]).unwrap();
                     Ok(ImprovementOrRsByChance(p))
+                } else if let Some(captures) = Regex::new(r"^late-acceptance-([1-9][0-9]*)$").unwrap().captures(input) {
+                    Ok(LateAcceptance(captures[1].parse::<usize>().unwrap()))
+                } else if let Some(captures) = Regex::new(r"^threshold-accepting-([0-9]+)(-([0-9]|[1-9][0-9]|100)%)?$").unwrap().captures(input) {
+                    let decrease_percentage = captures.get(3).map_or(1, |p| p.as_str().parse::<u8>().unwrap()); //default: 1%
+                    Ok(ThresholdAccepting(captures[1].parse::<u32>().unwrap(), decrease_percentage))
+                } else if let Some(captures) = Regex::new(r"^great-deluge-([0-9]+)$").unwrap().captures(input) {
+                    Ok(GreatDeluge(captures[1].parse::<u32>().unwrap()))
                 } else {
                     Err(format!("invalid variant: {input}"))
                 }
@@ -521,24 +1000,151 @@ pub struct SwapConfig {
     restart_scaling_factor: f64,
     random_restart_possibility: f64,
     lambda: f64,
+    //termination criteria (None => criterion not used):
+    max_steps: Option<usize>,
+    max_restarts: Option<usize>,
+    stagnation_limit: Option<usize>,
+    //restarts without improvement
+    time_budget: Option<f64>,
+    //in seconds
+    target_c_max: Option<u32>,
+    objective_kind: ObjectiveKind,
+    island_topology: Option<IslandTopology>,
+    //None => no island model (restarts use good_solutions)
+    migration_interval: usize,
+    //restarts between two migrations
+    migration_policy: MigrationPolicy,
+    island_size: usize,
+}
+
+impl SwapConfig {
+    /// default config using the given swap finding tactic and swap acceptance rule
+    pub fn new(swap_finding_tactic: SwapTactic, swap_acceptance_rule: SwapAcceptanceRule) -> Self {
+        Self { swap_finding_tactic, swap_acceptance_rule, ..Self::from_str("").unwrap() }
+    }
+
+    /// whether one of the configured termination criteria is reached
+    fn termination_reached(&self, steps: usize, restarts: usize, restarts_without_improvement: usize, best_c_max: u32, swap_start_time: Instant) -> bool {
+        self.max_steps.is_some_and(|max_steps| steps >= max_steps)
+            || self.max_restarts.is_some_and(|max_restarts| restarts >= max_restarts)
+            || self.stagnation_limit.is_some_and(|stagnation_limit| restarts_without_improvement >= stagnation_limit)
+            || self.time_budget.is_some_and(|time_budget| swap_start_time.elapsed().as_secs_f64() >= time_budget)
+            || self.target_c_max.is_some_and(|target_c_max| best_c_max <= target_c_max)
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct ConcreteSwapConfig {
-    swap_finding_tactic: fn(&Swapper, &Solution, &mut ConcreteSwapConfig) -> Option<(usize, usize, usize, i32)>,
-    swap_acceptance_rule: fn(u32, u32, &mut ConcreteSwapConfig) -> bool,
+    swap_finding_tactic: fn(&Swapper, &mut Solution, &mut ConcreteSwapConfig, bool) -> bool,
+    //performs one step (swap or redistribution of jobs) => false if no step was found
+    objective_kind: ObjectiveKind,
+    swap_acceptance_rule: fn(Objective, Objective, &mut ConcreteSwapConfig) -> bool,
+    needs_best_swap: fn(&mut ConcreteSwapConfig) -> bool,
+    //whether the 2 job best swap gets computed (false => random swap instead)
+    accepts_worsening_swaps: bool,
+    //whether the swap acceptance rule may accept non improving swaps (=> the 2 job best swap passes them to the rule)
     decline_by_chance_percentage: Option<u8>,
     random_swap_fails_until_stop: Option<usize>,
     rng: MyRng,
     improvement_or_rs_by_chance_percentage: Option<u8>,
+    late_acceptance_history_length: Option<usize>,
+    late_acceptance_history: Vec<Objective>,
+    //c_max of the last history_length iterations (gets filled with the first c_max)
+    acceptance_iteration: usize,
+    threshold_accepting_parameters: Option<(u32, u8)>,
+    //(initial_threshold, decrease_percentage)
+    threshold: f64,
+    great_deluge_rain_speed: Option<u32>,
+    water_level: Option<f64>, //None => gets set to the first c_max
+}
+
+impl ConcreteSwapConfig {
+    pub fn new(config: &SwapConfig, rng: MyRng) -> Self {
+        //new swap tactics can be added here:
+        let swap_finding_tactic_fn: fn(&Swapper, &mut Solution, &mut ConcreteSwapConfig, bool) -> bool = match config.swap_finding_tactic {
+            TwoJobBestSwap => Swapper::best_two_job_swap_step,
+            TwoJobRandomSwap(_) => Swapper::random_two_job_swap_step,
+            PairReoptimization => Swapper::pair_reoptimization_step,
+            AllPairsBestSwap => Swapper::all_pairs_best_swap_step,
+            AllPairsFirstSwap => Swapper::all_pairs_first_swap_step,
+        };
+
+        //new swap acceptance rules can be added here:
+        let swap_acceptance_rule_fn: fn(Objective, Objective, &mut ConcreteSwapConfig) -> bool = match config.swap_acceptance_rule {
+            Improvement => Swapper::accept_improvement,
+            DeclineByChance(_) => Swapper::accept_decline_by_chance_c,
+            All => Swapper::accept_all,
+            ImprovementOrRsByChance(_) => Swapper::accept_improvement_or_rs_by_chance_c,
+            LateAcceptance(_) => Swapper::accept_late_acceptance,
+            ThresholdAccepting(_, _) => Swapper::accept_threshold_accepting,
+            GreatDeluge(_) => Swapper::accept_great_deluge,
+        };
+        let needs_best_swap_fn: fn(&mut ConcreteSwapConfig) -> bool = match config.swap_acceptance_rule {
+            DeclineByChance(_) => Swapper::best_swap_by_decline_chance,
+            ImprovementOrRsByChance(_) => Swapper::accept_improvement_or_rs_by_chance,
+            Improvement | All | LateAcceptance(_) | ThresholdAccepting(_, _) | GreatDeluge(_) => Swapper::always_best_swap,
+        };
+        let accepts_worsening_swaps = matches!(config.swap_acceptance_rule, LateAcceptance(_) | ThresholdAccepting(_, _) | GreatDeluge(_));
+        let random_swap_fails_until_stop = match config.swap_finding_tactic {
+            TwoJobRandomSwap(fails_until_stop) => { Some(fails_until_stop) }
+            _ => { None }
+        };
+        let decline_by_chance_percentage = match config.swap_acceptance_rule {
+            DeclineByChance(percentage) => { Some(percentage) }
+            _ => { None }
+        };
+        let improvement_or_rs_by_chance_percentage = match config.swap_acceptance_rule {
+            ImprovementOrRsByChance(percentage) => { Some(percentage) }
+            _ => { None }
+        };
+        let late_acceptance_history_length = match config.swap_acceptance_rule {
+            LateAcceptance(history_length) => { Some(history_length) }
+            _ => { None }
+        };
+        let threshold_accepting_parameters = match config.swap_acceptance_rule {
+            ThresholdAccepting(initial_threshold, decrease_percentage) => { Some((initial_threshold, decrease_percentage)) }
+            _ => { None }
+        };
+        let great_deluge_rain_speed = match config.swap_acceptance_rule {
+            GreatDeluge(rain_speed) => { Some(rain_speed) }
+            _ => { None }
+        };
+
+        ConcreteSwapConfig {
+            swap_finding_tactic: swap_finding_tactic_fn,
+            objective_kind: config.objective_kind,
+            swap_acceptance_rule: swap_acceptance_rule_fn,
+            needs_best_swap: needs_best_swap_fn,
+            accepts_worsening_swaps,
+            decline_by_chance_percentage,
+            random_swap_fails_until_stop,
+            rng,
+            improvement_or_rs_by_chance_percentage,
+            late_acceptance_history_length,
+            late_acceptance_history: vec![],
+            acceptance_iteration: 0,
+            threshold_accepting_parameters,
+            threshold: threshold_accepting_parameters.map_or(0f64, |(initial_threshold, _)| initial_threshold as f64),
+            great_deluge_rain_speed,
+            water_level: None,
+        }
+    }
+
+    /// resets the state of the swap acceptance rule (history, threshold, water level), has to be called when the search continues with another solution
+    pub fn reset_acceptance_state(&mut self) {
+        self.late_acceptance_history.clear();
+        self.acceptance_iteration = 0;
+        self.threshold = self.threshold_accepting_parameters.map_or(0f64, |(initial_threshold, _)| initial_threshold as f64);
+        self.water_level = None;
+    }
 }
 
 impl FromStr for SwapConfig {
-    type Err = ParseError;
+    type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let parts: Vec<&str> = s.split(",").collect();
-        Ok(SwapConfig {
+        let config = SwapConfig {
             swap_finding_tactic: {
                 if parts[0].len() > 0 {
                     SwapTactic::from_str(parts[0]).unwrap()
@@ -619,6 +1225,159 @@ impl FromStr for SwapConfig {
                     0.5
                 }
             },
-        })
+            max_steps: {
+                if parts.len() > 9 && !parts[9].is_empty() {
+                    Some(parts[9].parse::<usize>().unwrap())
+                } else {
+                    //default:
+                    None
+                }
+            },
+            max_restarts: {
+                if parts.len() > 10 && !parts[10].is_empty() {
+                    Some(parts[10].parse::<usize>().unwrap())
+                } else {
+                    //default:
+                    None
+                }
+            },
+            stagnation_limit: {
+                if parts.len() > 11 && !parts[11].is_empty() {
+                    Some(parts[11].parse::<usize>().unwrap())
+                } else {
+                    //default:
+                    None
+                }
+            },
+            time_budget: {
+                if parts.len() > 12 && !parts[12].is_empty() {
+                    Some(parts[12].parse::<f64>().unwrap())
+                } else {
+                    //default:
+                    None
+                }
+            },
+            target_c_max: {
+                if parts.len() > 13 && !parts[13].is_empty() {
+                    Some(parts[13].parse::<u32>().unwrap())
+                } else {
+                    //default:
+                    None
+                }
+            },
+            objective_kind: {
+                if parts.len() > 14 && !parts[14].is_empty() {
+                    ObjectiveKind::from_str(parts[14]).unwrap()
+                } else {
+                    //default:
+                    CMax
+                }
+            },
+            island_topology: {
+                if parts.len() > 15 && !parts[15].is_empty() {
+                    Some(IslandTopology::from_str(parts[15]).unwrap())
+                } else {
+                    //default:
+                    None
+                }
+            },
+            migration_interval: {
+                if parts.len() > 16 && !parts[16].is_empty() {
+                    parts[16].parse::<usize>().unwrap()
+                } else {
+                    //default:
+                    10
+                }
+            },
+            migration_policy: {
+                if parts.len() > 17 && !parts[17].is_empty() {
+                    MigrationPolicy::from_str(parts[17]).unwrap()
+                } else {
+                    //default:
+                    BestMigrant
+                }
+            },
+            island_size: {
+                if parts.len() > 18 && !parts[18].is_empty() {
+                    parts[18].parse::<usize>().unwrap()
+                } else {
+                    //default:
+                    10
+                }
+            },
+        };
+        if config.migration_interval < 1 {
+            return Err(format!("migration_interval has to be at least 1: {s}"));
+        }
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input::RngSeed;
+
+    use super::*;
+
+    fn concrete_swap_config(swap_acceptance_rule: &str) -> ConcreteSwapConfig {
+        let config = SwapConfig::from_str(&format!("two-job-best-swap,{swap_acceptance_rule}")).unwrap();
+        ConcreteSwapConfig::new(&config, RngSeed::from_str("[1]").unwrap().create_rng())
+    }
+
+    fn objective(c_max: u32) -> Objective {
+        Objective::new(CMax, &MachineJobs::empty(1)).with_values(c_max, 0, 0)
+    }
+
+    fn accept(concrete_swap_config: &mut ConcreteSwapConfig, new_c_max: u32, old_c_max: u32) -> bool {
+        (concrete_swap_config.swap_acceptance_rule)(objective(new_c_max), objective(old_c_max), concrete_swap_config)
+    }
+
+    #[test]
+    fn late_acceptance_compares_with_the_history() {
+        let mut config = concrete_swap_config("late-acceptance-2");
+        assert!(config.accepts_worsening_swaps);
+        //history gets filled with 12:
+        assert!(accept(&mut config, 10, 12));
+        //worse than the current solution, but not than the history entry (12):
+        assert!(accept(&mut config, 11, 10));
+        //the history entry got replaced by the accepted 10:
+        assert!(!accept(&mut config, 11, 10));
+        assert_eq!(config.late_acceptance_history, vec![objective(10), objective(11)]);
+    }
+
+    #[test]
+    fn threshold_accepting_decreases_the_threshold() {
+        let mut config = concrete_swap_config("threshold-accepting-2-50%");
+        assert!(config.accepts_worsening_swaps);
+        assert!(accept(&mut config, 12, 10));
+        //threshold 2 => 1:
+        assert!(!accept(&mut config, 12, 10));
+        assert!(accept(&mut config, 11, 10));
+        assert_eq!(config.threshold, 0.5);
+        assert!(!accept(&mut config, 11, 10));
+        assert!(accept(&mut config, 9, 10));
+    }
+
+    #[test]
+    fn great_deluge_lowers_the_water_level() {
+        let mut config = concrete_swap_config("great-deluge-500");
+        assert!(config.accepts_worsening_swaps);
+        //water level starts at 12 => 11.5:
+        assert!(accept(&mut config, 11, 12));
+        assert!(accept(&mut config, 11, 10));
+        assert!(!accept(&mut config, 12, 10));
+        //water level 11 => 10.5:
+        assert!(accept(&mut config, 11, 10));
+        assert!(!accept(&mut config, 11, 10));
+        assert!(accept(&mut config, 9, 10));
+        config.reset_acceptance_state();
+        assert_eq!(config.water_level, None);
+    }
+
+    #[test]
+    fn only_rules_for_worsening_swaps_get_them() {
+        for rule in ["improvement", "all", "decline-by-50%-chance", "improvement-or-rs-by-50%-chance"] {
+            assert!(!concrete_swap_config(rule).accepts_worsening_swaps, "{rule}");
+        }
     }
 }
@@ -0,0 +1,292 @@
+use std::str::FromStr;
+use std::string::ParseError;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use permutation::Permutation;
+use rand::Rng;
+
+use crate::{Algorithm, Args};
+use crate::Algorithm::VNS;
+use crate::global_bounds::bounds::Bounds;
+use crate::good_solutions::good_solutions::GoodSolutions;
+use crate::input::input::Input;
+use crate::input::MyRng;
+use crate::logging::logger::{log, LogLevel};
+use crate::output::solution::Solution;
+use crate::schedulers::local_search::vns::Neighborhood::{OneOneSwap, Push, ThreeCycle, TwoOneSwap, TwoTwoSwap};
+use crate::schedulers::scheduler::Scheduler;
+
+pub struct VNSScheduler {
+    input: Arc<Input>,
+    global_bounds: Arc<Bounds>,
+    config: VNSConfig,
+    rng: MyRng,
+}
+
+impl Scheduler for VNSScheduler {
+    fn schedule(&mut self, good_solutions: GoodSolutions, args: Arc<Args>, _perm: Arc<Permutation>, start_time: Instant) -> Solution {
+        self.variable_neighborhood_search(good_solutions, args, start_time)
+    }
+
+    fn get_algorithm(&self) -> Algorithm {
+        VNS
+    }
+}
+
+///Neighborhood structure used by the variable neighborhood descent (all moves start at the heaviest machine)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Neighborhood {
+    ///move one job from the heaviest machine to another machine
+    Push,
+    ///exchange one job of the heaviest machine with one job of another machine
+    OneOneSwap,
+    ///exchange two jobs of the heaviest machine with one job of another machine
+    TwoOneSwap,
+    ///exchange two jobs of the heaviest machine with two jobs of another machine
+    TwoTwoSwap,
+    ///move one job each along the cycle heaviest machine -> m2 -> m3 -> heaviest machine
+    ThreeCycle,
+}
+
+/// a move is a sequence of pushes (machine_1_index, job_index_on_machine_1, machine_2_index) that are applied in this order
+type Move = Vec<(usize, usize, usize)>;
+
+impl VNSScheduler {
+    pub fn new(input: Arc<Input>, global_bounds: Arc<Bounds>, config: VNSConfig, shared_initial_rng: Arc<Mutex<MyRng>>) -> Self {
+        let rng = shared_initial_rng.lock().unwrap().generate_new_seed().create_rng();
+        Self { input, global_bounds, config, rng }
+    }
+
+    /// improves the best good solution with a VND and (if shaking_k_max > 0) escapes local optima by shaking it with k random moves
+    /// all improvements get stored in good_solutions, the best solution gets returned
+    fn variable_neighborhood_search(&mut self, good_solutions: GoodSolutions, args: Arc<Args>, start_time: Instant) -> Solution {
+        log(LogLevel::Debug, format!("running {:?} algorithm...", VNS), None);
+
+        log(LogLevel::Debug, String::from("waiting for enough good solutions to run VNS algorithm..."), Some(VNS));
+        if !good_solutions.wait_for_count(1, Some(Duration::from_secs(args.timeout_after).saturating_sub(start_time.elapsed()))) {
+            return Solution::unsatisfiable(VNS); //timeout
+        }
+
+        let mut best_solution = good_solutions.get_best_solution().unwrap();
+        best_solution.add_algorithm(VNS);
+        best_solution.add_config(format!("VNS_CONFIG: NEIGHBORHOODS:{:?}; SHAKING_K_MAX:{}; FAILS_UNTIL_STOP:{}; RNG:{}", self.config.neighborhoods, self.config.shaking_k_max, self.config.fails_until_stop, self.rng));
+        self.variable_neighborhood_descent(&mut best_solution);
+        self.store_solution(&best_solution, &good_solutions, Arc::clone(&args), start_time);
+
+        if self.input.get_machine_count() < 2 || self.config.shaking_k_max == 0 {
+            return best_solution; //pure VND
+        }
+
+        let mut fails = 0;
+        let mut k = 1;
+        while fails < self.config.fails_until_stop && !self.global_bounds.get_cancellation_token().is_cancelled() {
+            let mut solution = best_solution.clone();
+            self.shake(&mut solution, k);
+            self.variable_neighborhood_descent(&mut solution);
+
+            if solution.get_data().get_c_max() < best_solution.get_data().get_c_max() {
+                best_solution = solution;
+                self.store_solution(&best_solution, &good_solutions, Arc::clone(&args), start_time);
+                k = 1;
+                fails = 0;
+            } else {
+                fails += 1;
+                k = if k == self.config.shaking_k_max { 1 } else { k + 1 };
+            }
+        }
+
+        best_solution
+    }
+
+    /// goes to the next neighborhood when the current one yields no improvement and returns to the first one after an improvement
+    pub fn variable_neighborhood_descent(&self, solution: &mut Solution) {
+        let mut k = 0;
+        while k < self.config.neighborhoods.len() {
+            match self.find_improving_move(self.config.neighborhoods[k], solution) {
+                None => { k += 1; }
+                Some(pushes) => {
+                    for push_indices in pushes {
+                        solution.push_job(push_indices, self.input.get_jobs());
+                    }
+                    k = 0;
+                }
+            }
+        }
+    }
+
+    /// first improvement: returns the first move that lowers the workload of the heaviest machine without creating a new machine with a workload >= the old one
+    fn find_improving_move(&self, neighborhood: Neighborhood, solution: &Solution) -> Option<Move> {
+        let jobs = self.input.get_jobs();
+        let machine_jobs = solution.get_data().get_machine_jobs();
+        let h = machine_jobs.get_heaviest_machine_index();
+        let h_load = machine_jobs.get_machine_workload(h) as i64;
+        let h_jobs = machine_jobs.get_machine_jobs(h);
+
+        //partners are tried from the lightest to the heaviest machine:
+        let mut partners: Vec<usize> = (0..self.input.get_machine_count()).filter(|&m| m != h).collect();
+        partners.sort_by_key(|&m| machine_jobs.get_machine_workload(m));
+
+        for &p in &partners {
+            let p_load = machine_jobs.get_machine_workload(p) as i64;
+            let p_jobs = machine_jobs.get_machine_jobs(p);
+            let max_diff = h_load - p_load - 1; //the moved workload d has to fulfill 0 < d <= max_diff
+            if max_diff < 1 && neighborhood != ThreeCycle {
+                break; //all remaining partners are at least as heavy
+            }
+
+            match neighborhood {
+                Push => {
+                    for j1 in 0..h_jobs.len() {
+                        if jobs[h_jobs[j1]] as i64 <= max_diff {
+                            return Some(vec![(h, j1, p)]);
+                        }
+                    }
+                }
+                OneOneSwap => {
+                    for j1 in 0..h_jobs.len() {
+                        for j2 in 0..p_jobs.len() {
+                            let d = jobs[h_jobs[j1]] as i64 - jobs[p_jobs[j2]] as i64;
+                            if 0 < d && d <= max_diff {
+                                return Some(vec![(p, j2, h), (h, j1, p)]);
+                            }
+                        }
+                    }
+                }
+                TwoOneSwap => {
+                    for j1 in 0..h_jobs.len() {
+                        for j1b in j1 + 1..h_jobs.len() {
+                            for j2 in 0..p_jobs.len() {
+                                let d = jobs[h_jobs[j1]] as i64 + jobs[h_jobs[j1b]] as i64 - jobs[p_jobs[j2]] as i64;
+                                if 0 < d && d <= max_diff {
+                                    return Some(vec![(p, j2, h), (h, j1b, p), (h, j1, p)]);
+                                }
+                            }
+                        }
+                    }
+                }
+                TwoTwoSwap => {
+                    for j1 in 0..h_jobs.len() {
+                        for j1b in j1 + 1..h_jobs.len() {
+                            for j2 in 0..p_jobs.len() {
+                                for j2b in j2 + 1..p_jobs.len() {
+                                    let d = jobs[h_jobs[j1]] as i64 + jobs[h_jobs[j1b]] as i64 - jobs[p_jobs[j2]] as i64 - jobs[p_jobs[j2b]] as i64;
+                                    if 0 < d && d <= max_diff {
+                                        return Some(vec![(p, j2b, h), (p, j2, h), (h, j1b, p), (h, j1, p)]);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                ThreeCycle => {
+                    //h -(j1)-> p -(j2)-> q -(j3)-> h
+                    for &q in &partners {
+                        if q == p { continue; }
+                        let q_load = machine_jobs.get_machine_workload(q) as i64;
+                        let q_jobs = machine_jobs.get_machine_jobs(q);
+                        for j1 in 0..h_jobs.len() {
+                            for j2 in 0..p_jobs.len() {
+                                if p_load + jobs[h_jobs[j1]] as i64 - jobs[p_jobs[j2]] as i64 >= h_load { continue; }
+                                for j3 in 0..q_jobs.len() {
+                                    if jobs[q_jobs[j3]] < jobs[h_jobs[j1]] && q_load + jobs[p_jobs[j2]] as i64 - (jobs[q_jobs[j3]] as i64) < h_load {
+                                        return Some(vec![(p, j2, q), (q, j3, h), (h, j1, p)]);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// applies k random pushes or swaps between random machines
+    fn shake(&mut self, solution: &mut Solution, k: usize) {
+        let machine_count = self.input.get_machine_count();
+        for _ in 0..k {
+            let machine_jobs = solution.get_data().get_machine_jobs();
+            let mut m1 = self.rng.get_mut().gen_range(0..machine_count);
+            while machine_jobs.get_machine_jobs(m1).is_empty() {
+                m1 = self.rng.get_mut().gen_range(0..machine_count);
+            }
+            let mut m2 = self.rng.get_mut().gen_range(0..machine_count);
+            while m2 == m1 {
+                m2 = self.rng.get_mut().gen_range(0..machine_count);
+            }
+            let j1 = self.rng.get_mut().gen_range(0..machine_jobs.get_machine_jobs(m1).len());
+            let m2_job_count = machine_jobs.get_machine_jobs(m2).len();
+
+            if m2_job_count > 0 && self.rng.get_mut().gen_bool(0.5) {
+                //swap:
+                let j2 = self.rng.get_mut().gen_range(0..m2_job_count);
+                solution.swap_jobs((m1, j1, m2, j2 as i32), self.input.get_jobs(), false);
+            } else {
+                //push:
+                solution.push_job((m1, j1, m2), self.input.get_jobs());
+            }
+        }
+    }
+
+    fn store_solution(&self, solution: &Solution, good_solutions: &GoodSolutions, args: Arc<Args>, start_time: Instant) {
+        let mut solution = solution.clone();
+        self.global_bounds.update_upper_bound(solution.get_data().get_c_max(), &mut solution, args, start_time, Some(VNS));
+        good_solutions.add_solution(solution);
+    }
+}
+
+impl Neighborhood {
+    pub fn from_str(input: &str) -> Result<Self, String> {
+        match input {
+            "push" => Ok(Push),
+            "1-1-swap" => Ok(OneOneSwap),
+            "2-1-swap" => Ok(TwoOneSwap),
+            "2-2-swap" => Ok(TwoTwoSwap),
+            "3-cycle" => Ok(ThreeCycle),
+            _ => Err(format!("invalid variant: {input}"))
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct VNSConfig {
+    neighborhoods: Vec<Neighborhood>,
+    shaking_k_max: usize,
+    //0 => only VND
+    fails_until_stop: usize, //number of shakes without improvement until the search stops
+}
+
+impl FromStr for VNSConfig {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split(",").collect();
+        Ok(VNSConfig {
+            neighborhoods: {
+                if !parts[0].is_empty() {
+                    parts[0].split("/").map(|n| Neighborhood::from_str(n).unwrap()).collect()
+                } else {
+                    //default:
+                    vec![Push, OneOneSwap, TwoOneSwap, TwoTwoSwap, ThreeCycle]
+                }
+            },
+            shaking_k_max: {
+                if parts.len() > 1 && !parts[1].is_empty() {
+                    parts[1].parse::<usize>().unwrap()
+                } else {
+                    //default:
+                    5
+                }
+            },
+            fails_until_stop: {
+                if parts.len() > 2 && !parts[2].is_empty() {
+                    parts[2].parse::<usize>().unwrap()
+                } else {
+                    //default:
+                    100
+                }
+            },
+        })
+    }
+}
@@ -0,0 +1,238 @@
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use permutation::Permutation;
+use rand::Rng;
+use rayon::prelude::*;
+
+use crate::{Algorithm, Args};
+use crate::Algorithm::AntColony;
+use crate::global_bounds::bounds::Bounds;
+use crate::good_solutions::good_solutions::GoodSolutions;
+use crate::input::input::Input;
+use crate::input::{MyRng, RngSeed};
+use crate::logging::logger::{log, LogLevel};
+use crate::output::machine_jobs::MachineJobs;
+use crate::output::solution::Solution;
+use crate::schedulers::local_search::swapper::{ConcreteSwapConfig, SwapConfig, Swapper};
+use crate::schedulers::scheduler::Scheduler;
+
+pub struct AntColonyScheduler {
+    input: Arc<Input>,
+    global_bounds: Arc<Bounds>,
+    config: AntColonyConfig,
+    rng: MyRng,
+    swapper: Swapper,
+    //used for the optional local improvement of the ants
+}
+
+impl Scheduler for AntColonyScheduler {
+    fn schedule(&mut self, good_solutions: GoodSolutions, args: Arc<Args>, _perm: Arc<Permutation>, start_time: Instant) -> Solution {
+        self.ant_colony_optimization(good_solutions, args, start_time)
+    }
+
+    fn get_algorithm(&self) -> Algorithm {
+        AntColony
+    }
+}
+
+impl AntColonyScheduler {
+    pub fn new(input: Arc<Input>, global_bounds: Arc<Bounds>, config: AntColonyConfig, shared_initial_rng: Arc<Mutex<MyRng>>) -> Self {
+        let rng = shared_initial_rng.lock().unwrap().generate_new_seed().create_rng();
+        let swapper = Swapper::new(Arc::clone(&input), Arc::clone(&global_bounds), config.local_search_config.clone(), shared_initial_rng);
+        Self { input, global_bounds, config, rng, swapper }
+    }
+
+    /// each iteration the ants construct schedules in parallel (job-machine pheromone × slack under the upper bound), optionally improve them with the swap tactic
+    /// and the pheromone gets evaporated and reinforced by the iteration best and the best ant (max-min ant system => pheromone stays in [tau_min, tau_max])
+    /// all improvements get stored in good_solutions, the best ant gets returned
+    fn ant_colony_optimization(&mut self, good_solutions: GoodSolutions, args: Arc<Args>, start_time: Instant) -> Solution {
+        log(LogLevel::Debug, format!("running {:?} algorithm...", AntColony), None);
+
+        let jobs = self.input.get_jobs();
+        let machine_count = self.input.get_machine_count();
+        let config_str = format!("ANT_COLONY_CONFIG: ANTS:{}; ALPHA:{}; BETA:{}; EVAPORATION:{}; LOCAL_SEARCH_STEPS:{}; LOCAL_SEARCH_CONFIG:{:?}; FAILS_UNTIL_STOP:{}; RNG:{}", self.config.ants, self.config.alpha, self.config.beta, self.config.evaporation, self.config.local_search_steps, self.config.local_search_config, self.config.fails_until_stop, self.rng);
+
+        let tau_max = 1.0 / self.config.evaporation;
+        let tau_min = tau_max / (2 * machine_count) as f64;
+        let mut pheromones = vec![vec![tau_max; machine_count]; jobs.len()];
+        let mut best_solution: Option<Solution> = None;
+        let mut fails = 0;
+
+        while fails < self.config.fails_until_stop && !self.global_bounds.get_cancellation_token().is_cancelled() {
+            let upper_bound = self.global_bounds.get_upper_bound();
+            let seeds: Vec<(RngSeed, RngSeed)> = (0..self.config.ants).map(|_| (self.rng.generate_new_seed(), self.rng.generate_new_seed())).collect();
+
+            let ants: Vec<Solution> = seeds.par_iter().map(|(construction_seed, swap_seed)| {
+                let machine_jobs = self.construct(&pheromones, upper_bound, &mut construction_seed.create_rng());
+                let ant = Solution::new(AntColony, Some(config_str.clone()), machine_jobs, Arc::clone(&self.global_bounds), Arc::clone(&args), start_time);
+                if self.config.local_search_steps == 0 {
+                    return ant;
+                }
+                let mut concrete_swap_config = ConcreteSwapConfig::new(&self.config.local_search_config, swap_seed.create_rng());
+                let mut ant = self.swapper.local_search(ant, &mut concrete_swap_config, self.config.local_search_steps);
+                self.global_bounds.update_upper_bound(ant.get_data().get_c_max(), &mut ant, Arc::clone(&args), start_time, Some(AntColony));
+                ant
+            }).collect();
+
+            let iteration_best = ants.into_iter().min_by_key(|ant| ant.get_data().get_c_max()).unwrap();
+            if best_solution.as_ref().is_none_or(|best| iteration_best.get_data().get_c_max() < best.get_data().get_c_max()) {
+                good_solutions.add_solution(iteration_best.clone());
+                best_solution = Some(iteration_best.clone());
+                fails = 0;
+            } else {
+                fails += 1;
+            }
+
+            //evaporation:
+            for job_pheromones in pheromones.iter_mut() {
+                for pheromone in job_pheromones.iter_mut() {
+                    *pheromone *= 1.0 - self.config.evaporation;
+                }
+            }
+            //reinforcement (deposit = lower bound / c_max => better schedules deposit more):
+            let lower_bound = self.global_bounds.get_lower_bound() as f64;
+            for ant in [&iteration_best, best_solution.as_ref().unwrap()] {
+                let machine_jobs = ant.get_data().get_machine_jobs();
+                let deposit = lower_bound / ant.get_data().get_c_max() as f64;
+                for (machine_index, (_, job_indices)) in machine_jobs.as_slice().iter().enumerate() {
+                    for &job_index in job_indices {
+                        pheromones[job_index][machine_index] += deposit;
+                    }
+                }
+            }
+            for job_pheromones in pheromones.iter_mut() {
+                for pheromone in job_pheromones.iter_mut() {
+                    *pheromone = pheromone.clamp(tau_min, tau_max);
+                }
+            }
+        }
+
+        //no iteration ran (already cancelled or fails_until_stop=0):
+        best_solution.unwrap_or_else(|| Solution::unsatisfiable(AntColony))
+    }
+
+    /// assigns the jobs (descending by length) one by one, machine m is chosen with probability ~ pheromone^alpha * (slack + 1)^beta
+    /// (slack = upper_bound - new workload of m, machines that would reach the upper bound are only used if all would => least loaded one)
+    fn construct(&self, pheromones: &[Vec<f64>], upper_bound: u32, rng: &mut MyRng) -> MachineJobs {
+        let jobs = self.input.get_jobs();
+        let machine_count = self.input.get_machine_count();
+        let mut machine_jobs = MachineJobs::empty(machine_count);
+        let mut weights = vec![0.0; machine_count];
+
+        for (job_index, &job_length) in jobs.iter().enumerate() {
+            let mut weight_sum = 0.0;
+            for (machine_index, weight) in weights.iter_mut().enumerate() {
+                let new_workload = machine_jobs.get_machine_workload(machine_index) + job_length;
+                *weight = if new_workload < upper_bound {
+                    let slack = (upper_bound - new_workload) as f64;
+                    pheromones[job_index][machine_index].powf(self.config.alpha) * (slack + 1.0).powf(self.config.beta)
+                } else {
+                    0.0
+                };
+                weight_sum += *weight;
+            }
+
+            let machine_index = if weight_sum > 0.0 {
+                //roulette wheel selection:
+                let mut r = rng.get_mut().gen_range(0.0..weight_sum);
+                weights.iter().position(|&weight| {
+                    r -= weight;
+                    weight > 0.0 && r < 0.0
+                }).unwrap_or(machine_jobs.get_lightest_machine_index())
+            } else {
+                machine_jobs.get_lightest_machine_index()
+            };
+            machine_jobs.assign_job(job_length, machine_index, job_index);
+        }
+        machine_jobs
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AntColonyConfig {
+    ants: usize,
+    //ants per iteration
+    alpha: f64,
+    //influence of the pheromone
+    beta: f64,
+    //influence of the slack
+    evaporation: f64,
+    local_search_steps: usize,
+    //0 => no local improvement of the ants
+    local_search_config: SwapConfig,
+    //only the swap finding tactic and the swap acceptance rule are used
+    fails_until_stop: usize,
+    //iterations without improvement until the algorithm stops
+}
+
+impl FromStr for AntColonyConfig {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split(",").collect();
+        let config = AntColonyConfig {
+            ants: {
+                if !parts[0].is_empty() {
+                    parts[0].parse::<usize>().unwrap()
+                } else {
+                    //default:
+                    20
+                }
+            },
+            alpha: {
+                if parts.len() > 1 && !parts[1].is_empty() {
+                    parts[1].parse::<f64>().unwrap()
+                } else {
+                    //default:
+                    1.0
+                }
+            },
+            beta: {
+                if parts.len() > 2 && !parts[2].is_empty() {
+                    parts[2].parse::<f64>().unwrap()
+                } else {
+                    //default:
+                    2.0
+                }
+            },
+            evaporation: { //0.0-1.0 (exclusive)
+                if parts.len() > 3 && !parts[3].is_empty() {
+                    parts[3].parse::<f64>().unwrap()
+                } else {
+                    //default:
+                    0.1
+                }
+            },
+            local_search_steps: {
+                if parts.len() > 4 && !parts[4].is_empty() {
+                    parts[4].parse::<usize>().unwrap()
+                } else {
+                    //default:
+                    0
+                }
+            },
+            local_search_config: {
+                //"[swap_finding_tactic]/[swap_acceptance_rule]"
+                let local_search_config_str = if parts.len() > 5 { parts[5].replace('/', ",") } else { String::new() };
+                SwapConfig::from_str(&local_search_config_str).unwrap()
+            },
+            fails_until_stop: {
+                if parts.len() > 6 && !parts[6].is_empty() {
+                    parts[6].parse::<usize>().unwrap()
+                } else {
+                    //default:
+                    100
+                }
+            },
+        };
+        if config.ants < 1 {
+            return Err(format!("there has to be at least 1 ant: {s}"));
+        }
+        if !(config.evaporation > 0.0 && config.evaporation < 1.0) {
+            return Err(format!("the evaporation has to be in (0,1): {s}"));
+        }
+        Ok(config)
+    }
+}
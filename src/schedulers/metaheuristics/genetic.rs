@@ -0,0 +1,223 @@
+use std::str::FromStr;
+use std::string::ParseError;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use permutation::Permutation;
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+use crate::{Algorithm, Args};
+use crate::Algorithm::Genetic;
+use crate::global_bounds::bounds::Bounds;
+use crate::good_solutions::good_solutions::GoodSolutions;
+use crate::input::input::Input;
+use crate::input::MyRng;
+use crate::logging::logger::{log, LogLevel};
+use crate::output::machine_jobs::MachineJobs;
+use crate::output::solution::Solution;
+use crate::schedulers::local_search::swapper::{ConcreteSwapConfig, SwapConfig, Swapper};
+use crate::schedulers::scheduler::Scheduler;
+
+pub struct GeneticScheduler {
+    input: Arc<Input>,
+    global_bounds: Arc<Bounds>,
+    config: GeneticConfig,
+    rng: MyRng,
+    swapper: Swapper,
+    //used for the local improvement of the children
+    concrete_swap_config: ConcreteSwapConfig,
+}
+
+impl Scheduler for GeneticScheduler {
+    fn schedule(&mut self, good_solutions: GoodSolutions, args: Arc<Args>, _perm: Arc<Permutation>, start_time: Instant) -> Solution {
+        self.memetic_algorithm(good_solutions, args, start_time)
+    }
+
+    fn get_algorithm(&self) -> Algorithm {
+        Genetic
+    }
+}
+
+impl GeneticScheduler {
+    pub fn new(input: Arc<Input>, global_bounds: Arc<Bounds>, config: GeneticConfig, shared_initial_rng: Arc<Mutex<MyRng>>) -> Self {
+        let (rng, swap_rng) = {
+            let mut guard = shared_initial_rng.lock().unwrap();
+            (guard.generate_new_seed().create_rng(), guard.generate_new_seed().create_rng())
+        };
+        let concrete_swap_config = ConcreteSwapConfig::new(&config.local_search_config, swap_rng);
+        let swapper = Swapper::new(Arc::clone(&input), Arc::clone(&global_bounds), config.local_search_config.clone(), shared_initial_rng);
+        Self { input, global_bounds, config, rng, swapper, concrete_swap_config }
+    }
+
+    /// uses good_solutions as population: selects two parents, combines them (machine based crossover + LPT repair), mutates the child via random swaps,
+    /// improves it with the swap tactic and inserts it back into good_solutions
+    /// the best child gets returned
+    fn memetic_algorithm(&mut self, good_solutions: GoodSolutions, args: Arc<Args>, start_time: Instant) -> Solution {
+        log(LogLevel::Debug, format!("running {:?} algorithm...", Genetic), None);
+
+        log(LogLevel::Debug, String::from("waiting for enough good solutions to run Genetic algorithm..."), Some(Genetic));
+        if !good_solutions.wait_for_count(2, Some(Duration::from_secs(args.timeout_after).saturating_sub(start_time.elapsed()))) {
+            return Solution::unsatisfiable(Genetic); //timeout
+        }
+
+        let config_str = format!("GENETIC_CONFIG: FAILS_UNTIL_STOP:{}; MUTATION_POSSIBILITY:{}; MUTATION_SWAPS:{}; LOCAL_SEARCH_STEPS:{}; LOCAL_SEARCH_CONFIG:{:?}; LAMBDA:{}; RNG:{}", self.config.fails_until_stop, self.config.mutation_possibility, self.config.mutation_swaps, self.config.local_search_steps, self.config.local_search_config, self.config.lambda, self.rng);
+        let mut best_solution = good_solutions.get_best_solution().unwrap();
+        let mut fails = 0;
+
+        while fails < self.config.fails_until_stop && !self.global_bounds.get_cancellation_token().is_cancelled() {
+            let (parent_1, parent_2) = good_solutions.select_two_solutions(self.config.lambda, &mut self.rng);
+
+            let machine_jobs = self.crossover(parent_1.get_data().get_machine_jobs(), parent_2.get_data().get_machine_jobs());
+            let mut child = Solution::new(Genetic, Some(config_str.clone()), machine_jobs, Arc::clone(&self.global_bounds), Arc::clone(&args), start_time);
+
+            if self.rng.get_mut().gen_bool(self.config.mutation_possibility) {
+                self.mutate(&mut child);
+            }
+
+            let mut child = self.swapper.local_search(child, &mut self.concrete_swap_config, self.config.local_search_steps);
+            let child_c_max = child.get_data().get_c_max();
+            self.global_bounds.update_upper_bound(child_c_max, &mut child, Arc::clone(&args), start_time, Some(Genetic));
+            good_solutions.add_solution(child.clone());
+
+            if child_c_max < best_solution.get_data().get_c_max() {
+                best_solution = child;
+                fails = 0;
+            } else {
+                fails += 1;
+            }
+        }
+
+        best_solution
+    }
+
+    /// the child inherits complete machines alternately from both parents (only machines whose jobs are all still unassigned)
+    /// the remaining jobs get assigned to the least loaded machine (LPT)
+    fn crossover(&mut self, parent_1: &MachineJobs, parent_2: &MachineJobs) -> MachineJobs {
+        let jobs = self.input.get_jobs();
+        let machine_count = self.input.get_machine_count();
+
+        let mut parent_machines: Vec<Vec<usize>> = vec![(0..machine_count).collect(), (0..machine_count).collect()];
+        parent_machines[0].shuffle(self.rng.get_mut());
+        parent_machines[1].shuffle(self.rng.get_mut());
+        let parents = [parent_1, parent_2];
+
+        let mut machine_jobs = MachineJobs::empty(machine_count);
+        let mut assigned = vec![false; jobs.len()];
+        let mut next_machine = 0;
+        let mut positions = [0, 0]; //next machine position in parent_machines
+        let mut parent = self.rng.get_mut().gen_range(0..2);
+        while next_machine < machine_count && (positions[0] < machine_count || positions[1] < machine_count) {
+            if positions[parent] < machine_count {
+                let inherited_jobs = parents[parent].get_machine_jobs(parent_machines[parent][positions[parent]]);
+                positions[parent] += 1;
+                if !inherited_jobs.is_empty() && inherited_jobs.iter().all(|&job_index| !assigned[job_index]) {
+                    for &job_index in inherited_jobs {
+                        machine_jobs.assign_job(jobs[job_index], next_machine, job_index);
+                        assigned[job_index] = true;
+                    }
+                    next_machine += 1;
+                }
+            }
+            parent = 1 - parent;
+        }
+
+        //LPT repair:
+        for job_index in 0..jobs.len() {
+            if !assigned[job_index] {
+                machine_jobs.assign_job(jobs[job_index], machine_jobs.get_lightest_machine_index(), job_index);
+            }
+        }
+        machine_jobs
+    }
+
+    /// performs mutation_swaps random swaps
+    fn mutate(&mut self, solution: &mut Solution) {
+        let machine_count = self.input.get_machine_count();
+        if machine_count < 2 {
+            return;
+        }
+        for _ in 0..self.config.mutation_swaps {
+            let machine_jobs = solution.get_data().get_machine_jobs();
+            let m1 = self.rng.get_mut().gen_range(0..machine_count);
+            let mut m2 = self.rng.get_mut().gen_range(0..machine_count);
+            while m2 == m1 {
+                m2 = self.rng.get_mut().gen_range(0..machine_count);
+            }
+            let (m1_job_count, m2_job_count) = (machine_jobs.get_machine_jobs(m1).len(), machine_jobs.get_machine_jobs(m2).len());
+            if m1_job_count == 0 || m2_job_count == 0 {
+                continue;
+            }
+            let j1 = self.rng.get_mut().gen_range(0..m1_job_count);
+            let j2 = self.rng.get_mut().gen_range(0..m2_job_count);
+            solution.swap_jobs((m1, j1, m2, j2 as i32), self.input.get_jobs(), false);
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GeneticConfig {
+    fails_until_stop: usize,
+    //children without improvement until the algorithm stops
+    mutation_possibility: f64,
+    mutation_swaps: usize,
+    local_search_steps: usize,
+    local_search_config: SwapConfig,
+    //only the swap finding tactic and the swap acceptance rule are used
+    lambda: f64,
+}
+
+impl FromStr for GeneticConfig {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split(",").collect();
+        Ok(GeneticConfig {
+            fails_until_stop: {
+                if !parts[0].is_empty() {
+                    parts[0].parse::<usize>().unwrap()
+                } else {
+                    //default:
+                    1000
+                }
+            },
+            mutation_possibility: { //prozent 0.0-1.0
+                if parts.len() > 1 && !parts[1].is_empty() {
+                    parts[1].parse::<f64>().unwrap()
+                } else {
+                    //default:
+                    0.2
+                }
+            },
+            mutation_swaps: {
+                if parts.len() > 2 && !parts[2].is_empty() {
+                    parts[2].parse::<usize>().unwrap()
+                } else {
+                    //default:
+                    3
+                }
+            },
+            local_search_steps: {
+                if parts.len() > 3 && !parts[3].is_empty() {
+                    parts[3].parse::<usize>().unwrap()
+                } else {
+                    //default:
+                    1000
+                }
+            },
+            local_search_config: {
+                //"[swap_finding_tactic]/[swap_acceptance_rule]"
+                let local_search_config_str = if parts.len() > 4 { parts[4].replace('/', ",") } else { String::new() };
+                SwapConfig::from_str(&local_search_config_str).unwrap()
+            },
+            lambda: {
+                if parts.len() > 5 && !parts[5].is_empty() { //0.1 - inf
+                    parts[5].parse::<f64>().unwrap()
+                } else {
+                    //default:
+                    0.5
+                }
+            },
+        })
+    }
+}
@@ -0,0 +1,5 @@
+pub mod ant_colony;
+pub mod genetic;
+pub mod path_relinking;
+
+// Schedulers using population based metaheuristics to combine good solutions
@@ -0,0 +1,235 @@
+use std::cmp::max;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::string::ParseError;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use permutation::Permutation;
+
+use crate::{Algorithm, Args};
+use crate::Algorithm::PathRelinking;
+use crate::global_bounds::bounds::Bounds;
+use crate::good_solutions::good_solutions::GoodSolutions;
+use crate::input::input::Input;
+use crate::input::MyRng;
+use crate::logging::logger::{log, LogLevel};
+use crate::output::solution::Solution;
+use crate::schedulers::metaheuristics::path_relinking::RelinkingDirection::{Backward, Both, Forward};
+use crate::schedulers::scheduler::Scheduler;
+
+pub struct PathRelinkingScheduler {
+    input: Arc<Input>,
+    global_bounds: Arc<Bounds>,
+    config: PathRelinkingConfig,
+    rng: MyRng,
+}
+
+impl Scheduler for PathRelinkingScheduler {
+    fn schedule(&mut self, good_solutions: GoodSolutions, args: Arc<Args>, _perm: Arc<Permutation>, start_time: Instant) -> Solution {
+        self.path_relinking(good_solutions, args, start_time)
+    }
+
+    fn get_algorithm(&self) -> Algorithm {
+        PathRelinking
+    }
+}
+
+///Which of the two selected solutions is the initiating one (the guiding one is the other)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RelinkingDirection {
+    ///from the better solution to the worse one
+    Forward,
+    ///from the worse solution to the better one
+    Backward,
+    ///both paths
+    Both,
+}
+
+impl PathRelinkingScheduler {
+    pub fn new(input: Arc<Input>, global_bounds: Arc<Bounds>, config: PathRelinkingConfig, shared_initial_rng: Arc<Mutex<MyRng>>) -> Self {
+        let rng = shared_initial_rng.lock().unwrap().generate_new_seed().create_rng();
+        Self { input, global_bounds, config, rng }
+    }
+
+    /// repeatedly selects two good solutions and walks from the initiating one to the guiding one (greedily choosing the best job move each step)
+    /// all intermediate solutions that are better than both end points get stored in good_solutions, the best solution gets returned
+    fn path_relinking(&mut self, good_solutions: GoodSolutions, args: Arc<Args>, start_time: Instant) -> Solution {
+        log(LogLevel::Debug, format!("running {:?} algorithm...", PathRelinking), None);
+
+        log(LogLevel::Debug, String::from("waiting for enough good solutions to run PathRelinking algorithm..."), Some(PathRelinking));
+        if !good_solutions.wait_for_count(2, Some(Duration::from_secs(args.timeout_after).saturating_sub(start_time.elapsed()))) {
+            return Solution::unsatisfiable(PathRelinking); //timeout
+        }
+
+        let config_str = format!("PATH_RELINKING_CONFIG: FAILS_UNTIL_STOP:{}; DIRECTION:{:?}; LAMBDA:{}; RNG:{}", self.config.fails_until_stop, self.config.direction, self.config.lambda, self.rng);
+        let mut best_solution = good_solutions.get_best_solution().unwrap();
+        let mut fails = 0;
+
+        while fails < self.config.fails_until_stop && !self.global_bounds.get_cancellation_token().is_cancelled() {
+            let (better_solution, worse_solution) = self.select_solutions(&good_solutions);
+            let paths = match self.config.direction {
+                Forward => { vec![(better_solution, worse_solution)] }
+                Backward => { vec![(worse_solution, better_solution)] }
+                Both => { vec![(better_solution.clone(), worse_solution.clone()), (worse_solution, better_solution)] }
+            };
+
+            let mut improved = false;
+            for (initiating_solution, guiding_solution) in paths {
+                for mut solution in self.relink(initiating_solution, &guiding_solution, &config_str) {
+                    let c_max = solution.get_data().get_c_max();
+                    self.global_bounds.update_upper_bound(c_max, &mut solution, Arc::clone(&args), start_time, Some(PathRelinking));
+                    good_solutions.add_solution(solution.clone());
+                    if c_max < best_solution.get_data().get_c_max() {
+                        best_solution = solution;
+                        improved = true;
+                    }
+                }
+            }
+            fails = if improved { 0 } else { fails + 1 };
+        }
+
+        best_solution
+    }
+
+    /// chooses two different good solutions (see GoodSolutions::select_two_solutions) => (better one, worse one)
+    fn select_solutions(&mut self, good_solutions: &GoodSolutions) -> (Solution, Solution) {
+        let (solution_1, solution_2) = good_solutions.select_two_solutions(self.config.lambda, &mut self.rng);
+        if solution_1.get_data().get_c_max() <= solution_2.get_data().get_c_max() {
+            (solution_1, solution_2)
+        } else {
+            (solution_2, solution_1)
+        }
+    }
+
+    /// matches the machines of both solutions optimally, then moves jobs whose length is missing on the matched guiding machine to machines that lack this length
+    /// (each step performs the move with the best (c_max, sum of squared workloads)) until the initiating solution equals the guiding one
+    /// returns the intermediate solutions that improved the best c_max of the path so far (ascending by position on the path => descending by c_max)
+    fn relink(&self, mut solution: Solution, guiding_solution: &Solution, config_str: &str) -> Vec<Solution> {
+        let jobs = self.input.get_jobs();
+        let machine_count = self.input.get_machine_count();
+        let guiding_machine_jobs = guiding_solution.get_data().get_machine_jobs();
+        let matching = solution.get_data().get_machine_jobs().get_machine_matching(guiding_machine_jobs, jobs);
+
+        //lengths that machine m has more (excess) or less (deficit) than its guiding machine:
+        let mut excess_jobs: Vec<Vec<usize>> = vec![vec![]; machine_count];
+        let mut deficits: Vec<HashMap<u32, usize>> = vec![HashMap::new(); machine_count];
+        for m in 0..machine_count {
+            let mut balance: HashMap<u32, i64> = HashMap::new();
+            for &job_index in guiding_machine_jobs.get_machine_jobs(matching[m]) {
+                *balance.entry(jobs[job_index]).or_insert(0) -= 1;
+            }
+            for &job_index in solution.get_data().get_machine_jobs().get_machine_jobs(m) {
+                let job_balance = balance.entry(jobs[job_index]).or_insert(0);
+                *job_balance += 1;
+                if *job_balance > 0 {
+                    excess_jobs[m].push(job_index);
+                }
+            }
+            for (job_length, job_balance) in balance {
+                if job_balance < 0 {
+                    deficits[m].insert(job_length, (-job_balance) as usize);
+                }
+            }
+        }
+
+        let mut best_c_max = solution.get_data().get_c_max().min(guiding_solution.get_data().get_c_max()); //of both end points and the path so far
+        let mut improvements = vec![];
+        solution.add_algorithm(PathRelinking);
+        solution.add_config(config_str.to_string());
+
+        loop {
+            //best move (c_max, sum of squared workloads, machine_1, excess position, machine_2):
+            let machine_jobs = solution.get_data().get_machine_jobs();
+            let squared_workload_sum = machine_jobs.get_squared_workload_sum();
+            let mut best_move: Option<(u32, u64, usize, usize, usize)> = None;
+            for (m1, m1_excess_jobs) in excess_jobs.iter().enumerate() {
+                let m1_workload = machine_jobs.get_machine_workload(m1);
+                for (excess_index, &job_index) in m1_excess_jobs.iter().enumerate() {
+                    let job_length = jobs[job_index];
+                    for m2 in (0..machine_count).filter(|&m2| deficits[m2].contains_key(&job_length)) {
+                        let m2_workload = machine_jobs.get_machine_workload(m2);
+                        let c_max = max(machine_jobs.get_max_workload_without(&[m1, m2]), max(m1_workload - job_length, m2_workload + job_length));
+                        let square = |workload: u32| workload as u64 * workload as u64;
+                        let new_squared_workload_sum = squared_workload_sum + square(m1_workload - job_length) + square(m2_workload + job_length) - square(m1_workload) - square(m2_workload);
+                        if best_move.is_none_or(|(best_c_max, best_squared_workload_sum, _, _, _)| (c_max, new_squared_workload_sum) < (best_c_max, best_squared_workload_sum)) {
+                            best_move = Some((c_max, new_squared_workload_sum, m1, excess_index, m2));
+                        }
+                    }
+                }
+            }
+
+            let Some((c_max, _, m1, excess_index, m2)) = best_move else {
+                break; //guiding solution reached
+            };
+            let job_index = excess_jobs[m1].swap_remove(excess_index);
+            let job_length = jobs[job_index];
+            match deficits[m2].get_mut(&job_length) {
+                Some(count) if *count > 1 => { *count -= 1; }
+                _ => { deficits[m2].remove(&job_length); }
+            }
+            let job_index_on_machine = solution.get_data().get_machine_jobs().get_machine_jobs(m1).iter().position(|&j| j == job_index).unwrap();
+            solution.push_job((m1, job_index_on_machine, m2), jobs);
+
+            if c_max < best_c_max {
+                best_c_max = c_max;
+                improvements.push(solution.clone());
+            }
+        }
+
+        improvements
+    }
+}
+
+impl RelinkingDirection {
+    pub fn from_str(input: &str) -> Result<Self, String> {
+        match input {
+            "forward" => Ok(Forward),
+            "backward" => Ok(Backward),
+            "both" => Ok(Both),
+            _ => Err(format!("invalid variant: {input}"))
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PathRelinkingConfig {
+    fails_until_stop: usize,
+    //relinked pairs without improvement until the algorithm stops
+    direction: RelinkingDirection,
+    lambda: f64,
+}
+
+impl FromStr for PathRelinkingConfig {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split(",").collect();
+        Ok(PathRelinkingConfig {
+            fails_until_stop: {
+                if !parts[0].is_empty() {
+                    parts[0].parse::<usize>().unwrap()
+                } else {
+                    //default:
+                    100
+                }
+            },
+            direction: {
+                if parts.len() > 1 && !parts[1].is_empty() {
+                    RelinkingDirection::from_str(parts[1]).unwrap()
+                } else {
+                    //default:
+                    Both
+                }
+            },
+            lambda: {
+                if parts.len() > 2 && !parts[2].is_empty() { //0.1 - inf
+                    parts[2].parse::<f64>().unwrap()
+                } else {
+                    //default:
+                    0.5
+                }
+            },
+        })
+    }
+}
@@ -1,3 +1,4 @@
 pub mod list_schedulers;
 pub mod local_search;
+pub mod metaheuristics;
 pub mod scheduler;